@@ -0,0 +1,228 @@
+use std::collections::HashMap;
+
+use bevy::{ecs::system::SystemParam, prelude::*, window::PrimaryWindow};
+use serde::{Deserialize, Serialize};
+
+use crate::persistence::{PersistPlugin, Persistent};
+
+//how far the left stick has to be pushed before it counts as moving
+const MOVE_STICK_THRESHOLD: f32 = 0.5;
+//right stick inside this radius leaves the aim where it was
+const AIM_STICK_DEADZONE: f32 = 0.25;
+
+//only the button presses, aiming isn't one of them since it's a direction rather than a press.
+//it's always the cursor or the right stick, read through ActionInput::aim_cursor and aim_stick,
+//so there's nothing a single key could be rebound to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Action {
+    MoveLeft,
+    MoveRight,
+    Jump,
+    Fire,
+    NextWeapon,
+    PreviousWeapon,
+    Reload,
+    Pause,
+}
+
+impl Action {
+    pub const ALL: [Action; 8] = [
+        Action::MoveLeft,
+        Action::MoveRight,
+        Action::Jump,
+        Action::Fire,
+        Action::NextWeapon,
+        Action::PreviousWeapon,
+        Action::Reload,
+        Action::Pause,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Action::MoveLeft => "Move left",
+            Action::MoveRight => "Move right",
+            Action::Jump => "Jump",
+            Action::Fire => "Fire",
+            Action::NextWeapon => "Next weapon",
+            Action::PreviousWeapon => "Previous weapon",
+            Action::Reload => "Reload",
+            Action::Pause => "Pause",
+        }
+    }
+
+    //gamepad layout is fixed, only keyboard and mouse are rebindable
+    fn gamepad_buttons(&self) -> &'static [GamepadButtonType] {
+        match self {
+            Action::MoveLeft => &[GamepadButtonType::DPadLeft],
+            Action::MoveRight => &[GamepadButtonType::DPadRight],
+            Action::Jump => &[GamepadButtonType::South],
+            Action::Fire => &[GamepadButtonType::RightTrigger2],
+            Action::NextWeapon => &[GamepadButtonType::RightTrigger],
+            Action::PreviousWeapon => &[GamepadButtonType::LeftTrigger],
+            Action::Reload => &[GamepadButtonType::West],
+            Action::Pause => &[GamepadButtonType::Start],
+        }
+    }
+
+    fn default_binding(&self) -> Binding {
+        match self {
+            Action::MoveLeft => Binding::Key(KeyCode::A),
+            Action::MoveRight => Binding::Key(KeyCode::D),
+            Action::Jump => Binding::Key(KeyCode::W),
+            Action::Fire => Binding::Mouse(MouseButton::Left),
+            Action::NextWeapon => Binding::Key(KeyCode::E),
+            Action::PreviousWeapon => Binding::Key(KeyCode::Q),
+            Action::Reload => Binding::Key(KeyCode::R),
+            Action::Pause => Binding::Key(KeyCode::Escape),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Binding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+}
+
+impl Binding {
+    pub fn label(&self) -> String {
+        match self {
+            Binding::Key(key) => format!("{key:?}"),
+            Binding::Mouse(MouseButton::Left) => "Left mouse".to_string(),
+            Binding::Mouse(MouseButton::Right) => "Right mouse".to_string(),
+            Binding::Mouse(MouseButton::Middle) => "Middle mouse".to_string(),
+            Binding::Mouse(MouseButton::Other(button)) => format!("Mouse {button}"),
+        }
+    }
+}
+
+#[derive(Resource, Debug, Serialize, Deserialize)]
+pub struct Bindings(HashMap<Action, Binding>);
+
+impl Default for Bindings {
+    fn default() -> Self {
+        Bindings(
+            Action::ALL
+                .iter()
+                .map(|action| (*action, action.default_binding()))
+                .collect(),
+        )
+    }
+}
+
+impl Bindings {
+    //falls back to the default so config files from before an action existed still work
+    pub fn get(&self, action: Action) -> Binding {
+        self.0
+            .get(&action)
+            .copied()
+            .unwrap_or_else(|| action.default_binding())
+    }
+
+    pub fn set(&mut self, action: Action, binding: Binding) {
+        self.0.insert(action, binding);
+    }
+}
+
+impl Persistent for Bindings {
+    const FILE_NAME: &'static str = "controls.ron";
+    const VERSION: u32 = 1;
+}
+
+//what gameplay systems read instead of touching keys and mouse buttons directly
+#[derive(SystemParam)]
+pub struct ActionInput<'w, 's> {
+    bindings: Res<'w, Bindings>,
+    keys: Res<'w, Input<KeyCode>>,
+    mouse: Res<'w, Input<MouseButton>>,
+    gamepads: Res<'w, Gamepads>,
+    gamepad_buttons: Res<'w, Input<GamepadButton>>,
+    gamepad_axes: Res<'w, Axis<GamepadAxis>>,
+    primary_query: Query<'w, 's, &'static Window, With<PrimaryWindow>>,
+}
+
+impl<'w, 's> ActionInput<'w, 's> {
+    pub fn pressed(&self, action: Action) -> bool {
+        let bound = match self.bindings.get(action) {
+            Binding::Key(key) => self.keys.pressed(key),
+            Binding::Mouse(button) => self.mouse.pressed(button),
+        };
+
+        let stick_x = self
+            .stick(GamepadAxisType::LeftStickX, GamepadAxisType::LeftStickY)
+            .x;
+        let stick = match action {
+            Action::MoveLeft => stick_x < -MOVE_STICK_THRESHOLD,
+            Action::MoveRight => stick_x > MOVE_STICK_THRESHOLD,
+            _ => false,
+        };
+
+        bound || stick || self.gamepad_buttons(action, Input::pressed)
+    }
+
+    pub fn just_pressed(&self, action: Action) -> bool {
+        let bound = match self.bindings.get(action) {
+            Binding::Key(key) => self.keys.just_pressed(key),
+            Binding::Mouse(button) => self.mouse.just_pressed(button),
+        };
+
+        bound || self.gamepad_buttons(action, Input::just_pressed)
+    }
+
+    //cursor position in window coordinates
+    pub fn aim_cursor(&self) -> Option<Vec2> {
+        self.primary_query.get_single().ok()?.cursor_position()
+    }
+
+    //normalized right stick direction, none while it rests in the deadzone
+    pub fn aim_stick(&self) -> Option<Vec2> {
+        let stick = self.stick(GamepadAxisType::RightStickX, GamepadAxisType::RightStickY);
+
+        if stick.length() < AIM_STICK_DEADZONE {
+            return None;
+        }
+
+        Some(stick.normalize())
+    }
+
+    fn gamepad_buttons(
+        &self,
+        action: Action,
+        check: fn(&Input<GamepadButton>, GamepadButton) -> bool,
+    ) -> bool {
+        self.gamepads.iter().any(|gamepad| {
+            action.gamepad_buttons().iter().any(|button_type| {
+                check(
+                    &self.gamepad_buttons,
+                    GamepadButton::new(gamepad, *button_type),
+                )
+            })
+        })
+    }
+
+    //strongest stick out of every connected gamepad
+    fn stick(&self, x_axis: GamepadAxisType, y_axis: GamepadAxisType) -> Vec2 {
+        self.gamepads
+            .iter()
+            .map(|gamepad| {
+                Vec2::new(
+                    self.gamepad_axes
+                        .get(GamepadAxis::new(gamepad, x_axis))
+                        .unwrap_or(0.0),
+                    self.gamepad_axes
+                        .get(GamepadAxis::new(gamepad, y_axis))
+                        .unwrap_or(0.0),
+                )
+            })
+            .max_by(|a, b| a.length_squared().total_cmp(&b.length_squared()))
+            .unwrap_or(Vec2::ZERO)
+    }
+}
+
+pub struct ActionsPlugin;
+
+impl Plugin for ActionsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugin(PersistPlugin::<Bindings>::default());
+    }
+}
@@ -1,45 +1,218 @@
 use bevy::prelude::*;
+use bevy_rapier2d::prelude::Velocity;
 
 use crate::AppState;
 
 #[derive(Component)]
 pub struct GameCamera;
 
+//how the camera chases what it's following
+#[derive(Resource, Clone, Debug)]
+pub struct CameraRig {
+    //seconds to close half the distance to where it wants to be, the same at any frame rate
+    pub half_life: f32,
+    //half the size of the box around the middle of the screen the targets can move in
+    //without the camera following
+    pub dead_zone: Vec2,
+    //leads the targets by their velocity times this many seconds
+    pub look_ahead: Vec2,
+    pub max_look_ahead: Vec2,
+    //frames the targets this far below the middle, so more of what's above is in view
+    pub vertical_bias: f32,
+    //the camera's center never leaves this
+    pub bounds: Option<Rect>,
+}
+
+impl Default for CameraRig {
+    fn default() -> Self {
+        CameraRig {
+            half_life: 0.1,
+            dead_zone: Vec2::new(40.0, 30.0),
+            look_ahead: Vec2::new(0.25, 0.3),
+            max_look_ahead: Vec2::new(150.0, 200.0),
+            vertical_bias: 80.0,
+            bounds: None,
+        }
+    }
+}
+
+impl CameraRig {
+    //where the camera wants to be centered for targets at `focus` moving at `velocity`
+    pub fn goal(&self, focus: Vec2, velocity: Vec2) -> Vec2 {
+        let lead = (velocity * self.look_ahead).clamp(-self.max_look_ahead, self.max_look_ahead);
+
+        focus + lead + Vec2::new(0.0, self.vertical_bias)
+    }
+
+    //moves only as far as it takes to bring the goal back inside the dead zone
+    pub fn target(&self, camera: Vec2, goal: Vec2) -> Vec2 {
+        let offset = goal - camera;
+        let outside = (offset.abs() - self.dead_zone).max(Vec2::ZERO);
+
+        camera + outside * offset.signum()
+    }
+
+    pub fn step(&self, camera: Vec2, target: Vec2, delta_seconds: f32) -> Vec2 {
+        let remaining = if self.half_life > 0.0 {
+            0.5_f32.powf(delta_seconds / self.half_life)
+        } else {
+            0.0
+        };
+
+        let moved = target + (camera - target) * remaining;
+
+        match self.bounds {
+            Some(bounds) => moved.clamp(bounds.min, bounds.max),
+            None => moved,
+        }
+    }
+}
+
 fn create_camera(mut commands: Commands) {
     commands.spawn((Camera2dBundle::default(), GameCamera, Name::new("Camera")));
 }
 
-//breaks if following children
-//there should be only one entity with FollowedByCamera at once
+//anything the camera keeps in view, it frames the weighted average of all of them
+#[derive(Component, Clone, Copy, Debug)]
+pub struct FollowedByCamera {
+    pub weight: f32,
+}
+
+impl Default for FollowedByCamera {
+    fn default() -> Self {
+        FollowedByCamera { weight: 1.0 }
+    }
+}
+
 fn camera_follow(
-    mut camera_query: Query<&mut Transform, (With<GameCamera>, Without<FollowedByCamera>)>,
-    mut followed_by_camera_query: Query<
-        &mut Transform,
-        (With<FollowedByCamera>, Without<GameCamera>),
-    >,
+    mut camera_query: Query<&mut Transform, With<GameCamera>>,
+    followed_by_camera_query: Query<(&GlobalTransform, &FollowedByCamera, Option<&Velocity>)>,
+    rig: Res<CameraRig>,
+    time: Res<Time>,
 ) {
-    let mut camera = camera_query.single_mut();
-    let Ok(followed) = followed_by_camera_query.get_single_mut() else {
+    let Ok(mut camera) = camera_query.get_single_mut() else {
         return;
     };
 
-    let delta = followed.translation - camera.translation;
+    let mut total_weight = 0.0;
+    let mut focus = Vec2::ZERO;
+    let mut velocity = Vec2::ZERO;
+
+    for (transform, followed, followed_velocity) in followed_by_camera_query.iter() {
+        let weight = followed.weight.max(0.0);
+
+        total_weight += weight;
+        focus += transform.translation().truncate() * weight;
+        velocity += followed_velocity.map_or(Vec2::ZERO, |velocity| velocity.linvel) * weight;
+    }
+
+    if total_weight <= 0.0 {
+        return;
+    }
+
+    let goal = rig.goal(focus / total_weight, velocity / total_weight);
+    let position = camera.translation.truncate();
+    let target = rig.target(position, goal);
 
-    camera.translation = Vec3::new(
-        camera.translation.x + delta.x * 0.1,
-        camera.translation.y + delta.y * 0.1,
-        camera.translation.z,
-    )
+    camera.translation = rig
+        .step(position, target, time.delta_seconds())
+        .extend(camera.translation.z);
 }
 
 pub struct GameCameraPlugin;
 
-#[derive(Component)]
-pub struct FollowedByCamera;
-
 impl Plugin for GameCameraPlugin {
     fn build(&self, app: &mut App) {
-        app.add_startup_system(create_camera)
+        app.init_resource::<CameraRig>()
+            .add_startup_system(create_camera)
             .add_system(camera_follow.run_if(in_state(AppState::InGame)));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //runs the camera for `seconds` at a fixed frame rate towards a still target
+    fn settle(rig: &CameraRig, from: Vec2, to: Vec2, seconds: f32, fps: f32) -> Vec2 {
+        let delta_seconds = 1.0 / fps;
+        let mut camera = from;
+
+        for _ in 0..(seconds * fps).round() as usize {
+            camera = rig.step(camera, to, delta_seconds);
+        }
+
+        camera
+    }
+
+    #[test]
+    fn smoothing_does_not_depend_on_frame_rate() {
+        let rig = CameraRig::default();
+        let from = Vec2::ZERO;
+        let to = Vec2::new(300.0, 1200.0);
+
+        let reference = settle(&rig, from, to, 0.5, 60.0);
+
+        for fps in [20.0, 30.0, 144.0, 240.0] {
+            let camera = settle(&rig, from, to, 0.5, fps);
+            assert!(
+                camera.distance(reference) < 0.5,
+                "{fps} fps ended at {camera}, 60 fps at {reference}"
+            );
+        }
+    }
+
+    #[test]
+    fn converges_on_the_target() {
+        let rig = CameraRig::default();
+        let to = Vec2::new(-250.0, 4000.0);
+
+        for fps in [30.0, 60.0, 144.0] {
+            let camera = settle(&rig, Vec2::ZERO, to, 2.0, fps);
+            assert!(camera.distance(to) < 0.1, "{fps} fps ended at {camera}");
+        }
+    }
+
+    #[test]
+    fn half_life_halves_the_distance() {
+        let rig = CameraRig::default();
+        let to = Vec2::new(0.0, 100.0);
+
+        let camera = settle(&rig, Vec2::ZERO, to, rig.half_life, 120.0);
+        assert!((camera.y - 50.0).abs() < 0.01, "ended at {camera}");
+    }
+
+    #[test]
+    fn stays_put_inside_the_dead_zone() {
+        let rig = CameraRig::default();
+        let camera = Vec2::new(10.0, 20.0);
+
+        let goal = camera + rig.dead_zone * 0.9;
+        assert_eq!(rig.target(camera, goal), camera);
+
+        let goal = camera + Vec2::new(rig.dead_zone.x + 25.0, -rig.dead_zone.y - 5.0);
+        assert_eq!(rig.target(camera, goal), camera + Vec2::new(25.0, -5.0));
+    }
+
+    #[test]
+    fn look_ahead_is_capped() {
+        let rig = CameraRig {
+            vertical_bias: 0.0,
+            ..default()
+        };
+
+        let goal = rig.goal(Vec2::ZERO, Vec2::new(0.0, 100_000.0));
+        assert_eq!(goal, Vec2::new(0.0, rig.max_look_ahead.y));
+    }
+
+    #[test]
+    fn keeps_inside_the_bounds() {
+        let rig = CameraRig {
+            bounds: Some(Rect::new(-100.0, -100.0, 100.0, 100.0)),
+            ..default()
+        };
+
+        let camera = settle(&rig, Vec2::ZERO, Vec2::new(500.0, -500.0), 2.0, 60.0);
+        assert_eq!(camera, Vec2::new(100.0, -100.0));
+    }
+}
@@ -0,0 +1,589 @@
+use std::fs;
+
+use bevy::{
+    ecs::system::SystemParam, input::mouse::MouseMotion, prelude::*, window::PrimaryWindow,
+};
+
+use crate::{
+    actions::{Action, ActionInput},
+    camera::GameCamera,
+    game::platform::{
+        chunk::{ChunkPlatform, CustomLayout, LevelChunk},
+        PlatformKind, PLATFORM_HALF_SIZE,
+    },
+    persistence::ConfigDir,
+    AppState,
+};
+
+const GRID_SIZE: f32 = 10.0;
+const PAN_SPEED: f32 = 800.0;
+const ARM_SPAWNER_HALF_SIZE: f32 = 60.0;
+
+//where the player stands relative to the layout's origin once it's played
+const PLAYER_START: Vec2 = Vec2::new(0.0, 150.0);
+
+#[derive(Component, Clone, Copy, PartialEq)]
+enum EditorObject {
+    Platform { kind: PlatformKind, pickup: bool },
+    ArmSpawner,
+}
+
+impl EditorObject {
+    fn half_size(&self) -> Vec2 {
+        match self {
+            EditorObject::Platform { .. } => PLATFORM_HALF_SIZE,
+            EditorObject::ArmSpawner => Vec2::splat(ARM_SPAWNER_HALF_SIZE),
+        }
+    }
+}
+
+//what a left click on empty space places, one per platform kind and then arm spawners
+#[derive(Resource, Default)]
+struct EditorTool(usize);
+
+impl EditorTool {
+    const COUNT: usize = PlatformKind::ALL.len() + 1;
+
+    fn object(&self) -> EditorObject {
+        match PlatformKind::ALL.get(self.0) {
+            Some(kind) => EditorObject::Platform {
+                kind: *kind,
+                pickup: false,
+            },
+            None => EditorObject::ArmSpawner,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self.object() {
+            EditorObject::Platform { kind, .. } => kind.label(),
+            EditorObject::ArmSpawner => "Arm spawner",
+        }
+    }
+}
+
+//the object being dragged and where it was grabbed, relative to its center
+#[derive(Resource, Default)]
+struct Dragging(Option<(Entity, Vec2)>);
+
+//survives playtesting, none until the file has been read once
+#[derive(Resource, Default)]
+struct EditorLayout(Option<LevelChunk>);
+
+//last thing save or load did, shown under the controls
+#[derive(Resource, Default)]
+struct EditorStatus(String);
+
+#[derive(Component)]
+struct EditorEntity;
+
+#[derive(Component)]
+struct HelpText;
+
+fn write_layout(layout: &LevelChunk, config_dir: &ConfigDir) -> Result<(), String> {
+    let contents = ron::ser::to_string_pretty(layout, ron::ser::PrettyConfig::default())
+        .map_err(|err| err.to_string())?;
+    let path = CustomLayout::path(config_dir);
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|err| err.to_string())?;
+    }
+
+    fs::write(path, contents).map_err(|err| err.to_string())
+}
+
+fn snap(position: Vec2) -> Vec2 {
+    (position / GRID_SIZE).round() * GRID_SIZE
+}
+
+#[derive(SystemParam)]
+struct EditorCursor<'w, 's> {
+    window_query: Query<'w, 's, &'static Window, With<PrimaryWindow>>,
+    camera_query: Query<'w, 's, (&'static Camera, &'static GlobalTransform), With<GameCamera>>,
+}
+
+impl<'w, 's> EditorCursor<'w, 's> {
+    fn world_position(&self) -> Option<Vec2> {
+        let cursor = self.window_query.get_single().ok()?.cursor_position()?;
+        let (camera, camera_transform) = self.camera_query.get_single().ok()?;
+
+        camera
+            .viewport_to_world(camera_transform, cursor)
+            .map(|ray| ray.origin.truncate())
+    }
+}
+
+fn object_at(
+    position: Vec2,
+    objects: &Query<(Entity, &EditorObject, &Transform)>,
+) -> Option<Entity> {
+    objects
+        .iter()
+        .find(|(_, object, transform)| {
+            let offset = (position - transform.translation.truncate()).abs();
+            offset.x <= object.half_size().x && offset.y <= object.half_size().y
+        })
+        .map(|(entity, _, _)| entity)
+}
+
+fn spawn_editor_object(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    object: EditorObject,
+    position: Vec2,
+) {
+    let transform = Transform::from_translation(position.extend(1.0));
+
+    match object {
+        EditorObject::Platform { kind, pickup } => {
+            commands
+                .spawn((
+                    SpriteBundle {
+                        texture: asset_server.load("platform.png"),
+                        sprite: Sprite {
+                            color: kind.tint(),
+                            ..default()
+                        },
+                        transform,
+                        ..default()
+                    },
+                    object,
+                    Name::new("Editor platform"),
+                    EditorEntity,
+                ))
+                .with_children(|parent| {
+                    if pickup {
+                        parent.spawn(SpriteBundle {
+                            texture: asset_server.load("weapons/rocket_launcher/rocket.png"),
+                            transform: Transform {
+                                translation: Vec3::new(0.0, 90.0, 1.0),
+                                scale: Vec3::new(0.25, 0.25, 0.1),
+                                ..default()
+                            },
+                            ..default()
+                        });
+                    }
+                });
+        }
+        EditorObject::ArmSpawner => {
+            commands.spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: Color::rgba(1.0, 0.3, 0.3, 0.6),
+                        custom_size: Some(Vec2::splat(ARM_SPAWNER_HALF_SIZE * 2.0)),
+                        ..default()
+                    },
+                    transform,
+                    ..default()
+                },
+                object,
+                Name::new("Editor arm spawner"),
+                EditorEntity,
+            ));
+        }
+    }
+}
+
+fn layout_from_objects(objects: &Query<(Entity, &EditorObject, &Transform)>) -> LevelChunk {
+    let mut layout = LevelChunk::default();
+
+    for (_, object, transform) in objects.iter() {
+        let position = transform.translation.truncate();
+
+        match *object {
+            EditorObject::Platform { kind, pickup } => layout.platforms.push(ChunkPlatform {
+                position,
+                kind,
+                pickup,
+            }),
+            EditorObject::ArmSpawner => layout.enemy_spawns.push(position),
+        }
+    }
+
+    //bottom up, so the file reads in the order the level is climbed
+    layout
+        .platforms
+        .sort_by(|a, b| a.position.y.total_cmp(&b.position.y));
+
+    layout
+}
+
+fn spawn_layout(commands: &mut Commands, asset_server: &AssetServer, layout: &LevelChunk) {
+    for platform in layout.platforms.iter() {
+        spawn_editor_object(
+            commands,
+            asset_server,
+            EditorObject::Platform {
+                kind: platform.kind,
+                pickup: platform.pickup,
+            },
+            platform.position,
+        );
+    }
+
+    for enemy_spawn in layout.enemy_spawns.iter() {
+        spawn_editor_object(
+            commands,
+            asset_server,
+            EditorObject::ArmSpawner,
+            *enemy_spawn,
+        );
+    }
+}
+
+fn create_editor(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut editor_layout: ResMut<EditorLayout>,
+    mut status: ResMut<EditorStatus>,
+    mut camera_query: Query<&mut Transform, With<GameCamera>>,
+    config_dir: Res<ConfigDir>,
+) {
+    let layout = editor_layout
+        .0
+        .get_or_insert_with(|| match CustomLayout::read(&config_dir) {
+            Ok(layout) => {
+                status.0 = format!("Loaded {}", CustomLayout::path(&config_dir).display());
+                layout
+            }
+            //nothing saved yet, start from a lone platform at the origin
+            Err(_) => LevelChunk {
+                platforms: vec![ChunkPlatform {
+                    position: Vec2::ZERO,
+                    kind: PlatformKind::Static,
+                    pickup: false,
+                }],
+                ..default()
+            },
+        });
+
+    spawn_layout(&mut commands, &asset_server, layout);
+
+    if let Ok(mut camera) = camera_query.get_single_mut() {
+        camera.translation.x = PLAYER_START.x;
+        camera.translation.y = PLAYER_START.y;
+    }
+
+    commands.spawn((
+        SpriteBundle {
+            texture: asset_server.load("player/cupcake.png"),
+            sprite: Sprite {
+                color: Color::rgba(1.0, 1.0, 1.0, 0.4),
+                ..default()
+            },
+            transform: Transform {
+                translation: PLAYER_START.extend(0.5),
+                scale: Vec3::new(0.1, 0.1, 0.1),
+                ..default()
+            },
+            ..default()
+        },
+        Name::new("Player start"),
+        EditorEntity,
+    ));
+
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font: asset_server.load("century-gothic/gothic.ttf"),
+                font_size: 20.0,
+                color: Color::WHITE,
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                left: Val::Px(15.0),
+                top: Val::Px(10.0),
+                ..default()
+            },
+            ..default()
+        }),
+        HelpText,
+        EditorEntity,
+    ));
+}
+
+//keeps the edits around for when the editor is opened again
+fn stash_layout(
+    objects: Query<(Entity, &EditorObject, &Transform)>,
+    mut editor_layout: ResMut<EditorLayout>,
+) {
+    editor_layout.0 = Some(layout_from_objects(&objects));
+}
+
+fn despawn_editor(
+    mut commands: Commands,
+    editor_entities: Query<Entity, With<EditorEntity>>,
+    mut dragging: ResMut<Dragging>,
+) {
+    for entity in editor_entities.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    dragging.0 = None;
+}
+
+fn pan_editor_camera(
+    keys: Res<Input<KeyCode>>,
+    mouse_buttons: Res<Input<MouseButton>>,
+    mut ev_mouse_motion: EventReader<MouseMotion>,
+    mut camera_query: Query<&mut Transform, With<GameCamera>>,
+    time: Res<Time>,
+) {
+    let Ok(mut camera) = camera_query.get_single_mut() else {
+        return;
+    };
+
+    //ctrl+s is saving, not panning
+    let holding_control = keys.any_pressed([KeyCode::LControl, KeyCode::RControl]);
+    let mut direction = Vec2::ZERO;
+
+    if keys.any_pressed([KeyCode::Left, KeyCode::A]) {
+        direction.x -= 1.0;
+    }
+    if keys.any_pressed([KeyCode::Right, KeyCode::D]) {
+        direction.x += 1.0;
+    }
+    if keys.any_pressed([KeyCode::Down, KeyCode::S]) {
+        direction.y -= 1.0;
+    }
+    if keys.any_pressed([KeyCode::Up, KeyCode::W]) {
+        direction.y += 1.0;
+    }
+
+    if holding_control {
+        direction = Vec2::ZERO;
+    }
+
+    let mut delta = direction * PAN_SPEED * time.delta_seconds();
+
+    //dragging with the middle button grabs the level, window y points down
+    let motion: Vec2 = ev_mouse_motion.iter().map(|motion| motion.delta).sum();
+    if mouse_buttons.pressed(MouseButton::Middle) {
+        delta += Vec2::new(-motion.x, motion.y);
+    }
+
+    camera.translation += delta.extend(0.0);
+}
+
+fn select_tool(keys: Res<Input<KeyCode>>, mut tool: ResMut<EditorTool>) {
+    if keys.just_pressed(KeyCode::Tab) {
+        let step = if keys.pressed(KeyCode::LShift) {
+            EditorTool::COUNT - 1
+        } else {
+            1
+        };
+
+        tool.0 = (tool.0 + step) % EditorTool::COUNT;
+    }
+}
+
+//left click grabs whatever is under the cursor, or places the current tool on empty space
+fn place_or_grab(
+    mouse_buttons: Res<Input<MouseButton>>,
+    cursor: EditorCursor,
+    objects: Query<(Entity, &EditorObject, &Transform)>,
+    tool: Res<EditorTool>,
+    mut dragging: ResMut<Dragging>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    if !mouse_buttons.just_pressed(MouseButton::Left) {
+        return;
+    }
+
+    let Some(position) = cursor.world_position() else {
+        return;
+    };
+
+    if let Some(entity) = object_at(position, &objects) {
+        let Ok((_, _, transform)) = objects.get(entity) else {
+            return;
+        };
+
+        dragging.0 = Some((entity, position - transform.translation.truncate()));
+    } else {
+        spawn_editor_object(&mut commands, &asset_server, tool.object(), snap(position));
+    }
+}
+
+fn drag_object(
+    mouse_buttons: Res<Input<MouseButton>>,
+    cursor: EditorCursor,
+    mut transforms: Query<&mut Transform, With<EditorObject>>,
+    mut dragging: ResMut<Dragging>,
+) {
+    let Some((entity, grab_offset)) = dragging.0 else {
+        return;
+    };
+
+    if !mouse_buttons.pressed(MouseButton::Left) {
+        dragging.0 = None;
+        return;
+    }
+
+    let (Some(position), Ok(mut transform)) = (cursor.world_position(), transforms.get_mut(entity))
+    else {
+        return;
+    };
+
+    let snapped = snap(position - grab_offset);
+    transform.translation.x = snapped.x;
+    transform.translation.y = snapped.y;
+}
+
+fn delete_object(
+    mouse_buttons: Res<Input<MouseButton>>,
+    cursor: EditorCursor,
+    objects: Query<(Entity, &EditorObject, &Transform)>,
+    mut commands: Commands,
+) {
+    if !mouse_buttons.just_pressed(MouseButton::Right) {
+        return;
+    }
+
+    if let Some(entity) = cursor
+        .world_position()
+        .and_then(|position| object_at(position, &objects))
+    {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn toggle_pickup(
+    keys: Res<Input<KeyCode>>,
+    cursor: EditorCursor,
+    objects: Query<(Entity, &EditorObject, &Transform)>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    if !keys.just_pressed(KeyCode::F) {
+        return;
+    }
+
+    let Some(entity) = cursor
+        .world_position()
+        .and_then(|position| object_at(position, &objects))
+    else {
+        return;
+    };
+
+    let Ok((_, EditorObject::Platform { kind, pickup }, transform)) = objects.get(entity) else {
+        return;
+    };
+
+    //respawned rather than patched, the pickup sprite is a child
+    commands.entity(entity).despawn_recursive();
+    spawn_editor_object(
+        &mut commands,
+        &asset_server,
+        EditorObject::Platform {
+            kind: *kind,
+            pickup: !pickup,
+        },
+        transform.translation.truncate(),
+    );
+}
+
+fn save_or_load_layout(
+    keys: Res<Input<KeyCode>>,
+    objects: Query<(Entity, &EditorObject, &Transform)>,
+    mut status: ResMut<EditorStatus>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    config_dir: Res<ConfigDir>,
+) {
+    if !keys.any_pressed([KeyCode::LControl, KeyCode::RControl]) {
+        return;
+    }
+
+    if keys.just_pressed(KeyCode::S) {
+        status.0 = match write_layout(&layout_from_objects(&objects), &config_dir) {
+            Ok(()) => format!("Saved {}", CustomLayout::path(&config_dir).display()),
+            Err(err) => format!("Could not save: {err}"),
+        };
+    } else if keys.just_pressed(KeyCode::L) {
+        match CustomLayout::read(&config_dir) {
+            Ok(layout) => {
+                for (entity, _, _) in objects.iter() {
+                    commands.entity(entity).despawn_recursive();
+                }
+
+                spawn_layout(&mut commands, &asset_server, &layout);
+                status.0 = format!("Loaded {}", CustomLayout::path(&config_dir).display());
+            }
+            Err(err) => status.0 = format!("Could not load: {err}"),
+        }
+    }
+}
+
+fn leave_editor(
+    keys: Res<Input<KeyCode>>,
+    actions: ActionInput,
+    objects: Query<(Entity, &EditorObject, &Transform)>,
+    mut commands: Commands,
+) {
+    if keys.just_pressed(KeyCode::P) {
+        commands.insert_resource(CustomLayout(Some(layout_from_objects(&objects))));
+        commands.insert_resource(NextState(Some(AppState::InGame)));
+    } else if actions.just_pressed(Action::Pause) {
+        commands.insert_resource(NextState(Some(AppState::MainMenu)));
+    }
+}
+
+fn update_help_text(
+    tool: Res<EditorTool>,
+    status: Res<EditorStatus>,
+    mut help_text_query: Query<&mut Text, With<HelpText>>,
+) {
+    let Ok(mut text) = help_text_query.get_single_mut() else {
+        return;
+    };
+
+    if !tool.is_changed() && !status.is_changed() && !text.is_added() {
+        return;
+    }
+
+    text.sections[0].value = format!(
+        "Placing: {} (Tab to change)\n\
+        Left click: place / drag   Right click: delete   F: toggle pickup\n\
+        WASD / middle drag: pan   Ctrl+S: save   Ctrl+L: load\n\
+        P: playtest   Esc: main menu\n\
+        {}",
+        tool.label(),
+        status.0,
+    );
+}
+
+pub struct EditorPlugin;
+
+impl Plugin for EditorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EditorTool>()
+            .init_resource::<Dragging>()
+            .init_resource::<EditorLayout>()
+            .init_resource::<EditorStatus>()
+            .add_system(create_editor.in_schedule(OnEnter(AppState::Editor)))
+            .add_systems(
+                (stash_layout, despawn_editor)
+                    .chain()
+                    .in_schedule(OnExit(AppState::Editor)),
+            )
+            .add_systems(
+                (
+                    pan_editor_camera,
+                    select_tool,
+                    place_or_grab,
+                    drag_object.after(place_or_grab),
+                    delete_object,
+                    toggle_pickup,
+                    save_or_load_layout,
+                    leave_editor,
+                    update_help_text,
+                )
+                    .in_set(OnUpdate(AppState::Editor)),
+            );
+    }
+}
@@ -0,0 +1,96 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use rand::Rng;
+
+use crate::AppState;
+
+use super::{
+    platform::{Handcrafted, Platform},
+    seed::RunRng,
+    weapon::WeaponHolder,
+    PausedState,
+};
+
+//chance a new platform gets a pickup on top
+const PICKUP_CHANCE: f64 = 0.3;
+
+#[derive(Component)]
+pub struct AmmoPickup;
+
+//how much each weapon gets out of it is up to its definition
+pub struct AmmoPickedUp;
+
+//sits on top of the platform and goes away with it
+pub fn spawn_ammo_pickup(commands: &mut Commands, asset_server: &AssetServer, platform: Entity) {
+    let pickup = commands
+        .spawn((
+            AmmoPickup,
+            SpriteBundle {
+                texture: asset_server.load("weapons/rocket_launcher/rocket.png"),
+                transform: Transform {
+                    translation: Vec3::new(0.0, 90.0, 1.0),
+                    scale: Vec3::new(0.25, 0.25, 0.1),
+                    ..default()
+                },
+                ..default()
+            },
+            Collider::ball(150.0),
+            Sensor,
+            ActiveEvents::COLLISION_EVENTS,
+            Name::new("Ammo pickup"),
+        ))
+        .id();
+
+    commands.entity(platform).push_children(&[pickup]);
+}
+
+//chunks place their own pickups
+fn spawn_ammo_pickups(
+    new_platforms: Query<Entity, (Added<Platform>, Without<Handcrafted>)>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut run_rng: ResMut<RunRng>,
+) {
+    let rand_gen = &mut run_rng.pickups;
+
+    for platform in new_platforms.iter() {
+        if !rand_gen.gen_bool(PICKUP_CHANCE) {
+            continue;
+        }
+
+        spawn_ammo_pickup(&mut commands, &asset_server, platform);
+    }
+}
+
+fn pick_up_ammo(
+    mut ev_collision: EventReader<CollisionEvent>,
+    pickups: Query<(), With<AmmoPickup>>,
+    weapon_holders: Query<(), With<WeaponHolder>>,
+    mut commands: Commands,
+    mut ev_ammo_picked_up: EventWriter<AmmoPickedUp>,
+) {
+    for collision_event in ev_collision.iter() {
+        let CollisionEvent::Started(h1, h2, _event_flag) = collision_event else {
+            continue;
+        };
+
+        for (pickup, holder) in [(*h1, *h2), (*h2, *h1)] {
+            if pickups.contains(pickup) && weapon_holders.contains(holder) {
+                commands.entity(pickup).despawn_recursive();
+                ev_ammo_picked_up.send(AmmoPickedUp);
+            }
+        }
+    }
+}
+
+pub struct AmmoPickupPlugin;
+
+impl Plugin for AmmoPickupPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<AmmoPickedUp>().add_systems(
+            (spawn_ammo_pickups, pick_up_ammo)
+                .in_set(OnUpdate(AppState::InGame))
+                .in_set(OnUpdate(PausedState::Playing)),
+        );
+    }
+}
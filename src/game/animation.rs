@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+
+use bevy::{asset::HandleId, prelude::*, reflect::TypeUuid};
+use serde::Deserialize;
+
+use crate::{utils::ron_asset::RonAssetPlugin, AppState};
+
+use super::PausedState;
+
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PlayMode {
+    #[default]
+    Loop,
+    //stops on the last frame and sends AnimationFinished
+    Once,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct AnimationClip {
+    //atlas indices, in the order they play
+    pub frames: Vec<usize>,
+    #[serde(default = "default_fps")]
+    pub fps: f32,
+    #[serde(default)]
+    pub mode: PlayMode,
+}
+
+fn default_fps() -> f32 {
+    10.0
+}
+
+//a sprite sheet cut into a grid, and the named clips that play over it, see the .anim.ron files
+#[derive(Deserialize, TypeUuid, Debug)]
+#[uuid = "9a4c2e71-0b3d-4f85-b6e2-3d7f1c8a5e94"]
+pub struct AnimationSheet {
+    texture: String,
+    tile_size: Vec2,
+    columns: usize,
+    rows: usize,
+    clips: HashMap<String, AnimationClip>,
+}
+
+//the atlas each loaded sheet got cut into
+#[derive(Resource, Default)]
+struct SheetAtlases(HashMap<HandleId, Handle<TextureAtlas>>);
+
+//plays clips from a sheet on the entity's TextureAtlasSprite. the atlas handle gets
+//filled in once the sheet is loaded, so a default one is fine when spawning
+#[derive(Component)]
+pub struct SpriteAnimation {
+    sheet: Handle<AnimationSheet>,
+    clip: String,
+    frame: usize,
+    timer: Timer,
+    finished: bool,
+}
+
+impl SpriteAnimation {
+    pub fn new(sheet: Handle<AnimationSheet>, clip: &str) -> Self {
+        SpriteAnimation {
+            sheet,
+            clip: clip.to_string(),
+            frame: 0,
+            timer: Timer::from_seconds(1.0 / default_fps(), TimerMode::Repeating),
+            finished: false,
+        }
+    }
+
+    //starts the clip over from its first frame, unless it's the one already playing
+    pub fn play(&mut self, clip: &str) {
+        if self.clip == clip {
+            return;
+        }
+
+        self.clip = clip.to_string();
+        self.frame = 0;
+        self.timer.reset();
+        self.finished = false;
+    }
+
+    pub fn clip(&self) -> &str {
+        &self.clip
+    }
+}
+
+pub struct AnimationFinished {
+    pub entity: Entity,
+    pub clip: String,
+}
+
+fn build_atlases(
+    mut ev_sheets: EventReader<AssetEvent<AnimationSheet>>,
+    sheets: Res<Assets<AnimationSheet>>,
+    mut atlases: ResMut<SheetAtlases>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+    asset_server: Res<AssetServer>,
+) {
+    for ev_sheet in ev_sheets.iter() {
+        match ev_sheet {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => {
+                let Some(sheet) = sheets.get(handle) else {
+                    continue;
+                };
+
+                let texture_atlas = TextureAtlas::from_grid(
+                    asset_server.load(sheet.texture.as_str()),
+                    sheet.tile_size,
+                    sheet.columns,
+                    sheet.rows,
+                    None,
+                    None,
+                );
+
+                atlases
+                    .0
+                    .insert(handle.id(), texture_atlases.add(texture_atlas));
+            }
+            AssetEvent::Removed { handle } => {
+                atlases.0.remove(&handle.id());
+            }
+        }
+    }
+}
+
+fn animate_sprites(
+    mut animated: Query<(
+        Entity,
+        &mut SpriteAnimation,
+        &mut TextureAtlasSprite,
+        &mut Handle<TextureAtlas>,
+    )>,
+    sheets: Res<Assets<AnimationSheet>>,
+    atlases: Res<SheetAtlases>,
+    time: Res<Time>,
+    mut ev_finished: EventWriter<AnimationFinished>,
+) {
+    for (entity, mut animation, mut sprite, mut atlas) in animated.iter_mut() {
+        let (Some(sheet), Some(sheet_atlas)) = (
+            sheets.get(&animation.sheet),
+            atlases.0.get(&animation.sheet.id()),
+        ) else {
+            continue;
+        };
+
+        if *atlas != *sheet_atlas {
+            *atlas = sheet_atlas.clone();
+        }
+
+        let Some(clip) = sheet.clips.get(&animation.clip) else {
+            warn!("animation clip {} not in its sheet", animation.clip);
+            continue;
+        };
+
+        if clip.frames.is_empty() {
+            continue;
+        }
+
+        let frame_duration = std::time::Duration::from_secs_f32(1.0 / clip.fps.max(0.01));
+        if animation.timer.duration() != frame_duration {
+            animation.timer.set_duration(frame_duration);
+        }
+
+        if !animation.finished {
+            animation.timer.tick(time.delta());
+
+            let last = clip.frames.len() - 1;
+            let next = animation.frame + animation.timer.times_finished_this_tick() as usize;
+
+            animation.frame = match clip.mode {
+                PlayMode::Loop => next % clip.frames.len(),
+                PlayMode::Once => next.min(last),
+            };
+
+            //the last frame gets its full time on screen before it counts as done
+            if clip.mode == PlayMode::Once && next > last {
+                animation.finished = true;
+                ev_finished.send(AnimationFinished {
+                    entity,
+                    clip: animation.clip.clone(),
+                });
+            }
+        }
+
+        let index = clip.frames[animation.frame.min(clip.frames.len() - 1)];
+        if sprite.index != index {
+            sprite.index = index;
+        }
+    }
+}
+
+pub struct AnimationPlugin;
+
+impl Plugin for AnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugin(RonAssetPlugin::<AnimationSheet>::new(&["anim.ron"]))
+            .init_resource::<SheetAtlases>()
+            .add_event::<AnimationFinished>()
+            .add_system(build_atlases)
+            .add_system(
+                animate_sprites
+                    .after(build_atlases)
+                    .in_set(OnUpdate(AppState::InGame))
+                    .in_set(OnUpdate(PausedState::Playing)),
+            );
+    }
+}
@@ -0,0 +1,279 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::{
+    actions::{Action, ActionInput},
+    camera::FollowedByCamera,
+    game::{
+        animation::SpriteAnimation,
+        enemy::HurtsPlayer,
+        health::{Damage, Died, Health},
+        player::PlayerDied,
+        projectile::Projectile,
+        GameEntity,
+    },
+    game_audio::{PlaySfx, SfxCue},
+};
+
+use super::{Arms, ArmsTarget, GrabHitbox};
+
+//how long the player has to break free before being dragged off
+const GRAB_WINDOW_SECONDS: f32 = 3.0;
+
+//how fast the hand sinks back down with the player in it
+const RETREAT_SPEED: f32 = 250.0;
+
+//how far towards breaking free each kind of struggle gets, 1 is free
+const MASH_PROGRESS: f32 = 0.08;
+const SHOT_PROGRESS: f32 = 0.2;
+const HIT_PROGRESS: f32 = 0.5;
+
+//struggle progress lost per second, so it has to be kept up
+const PROGRESS_DECAY: f32 = 0.15;
+
+//what the player is left with after getting away
+const ESCAPE_HEALTH: u32 = 1;
+const ESCAPE_LAUNCH_SPEED: f32 = 600.0;
+
+//on the hand holding the player
+#[derive(Component)]
+pub(super) struct Grabbing {
+    window: Timer,
+    escape: f32,
+}
+
+//on a hand the player broke free of, it sinks away without chasing or hurting anyone
+#[derive(Component)]
+pub(super) struct Released;
+
+//on the player, who follows the hand around until this is resolved
+#[derive(Component)]
+pub struct Grabbed {
+    hand: Entity,
+}
+
+#[derive(Component)]
+pub(super) struct StrugglePrompt;
+
+#[derive(Component)]
+pub(super) struct StruggleBarFill;
+
+//only a hit that finishes the player off turns into a grab, a graze just hurts
+pub(super) fn grab_target(
+    mut ev_died: EventReader<Died>,
+    target_query: Query<(), With<ArmsTarget>>,
+    mut commands: Commands,
+    mut hands_query: Query<(&mut SpriteAnimation, &mut Velocity), With<Arms>>,
+    asset_server: Res<AssetServer>,
+    mut ev_sfx: EventWriter<PlaySfx>,
+) {
+    for died in ev_died.iter() {
+        if !target_query.contains(died.entity) {
+            continue;
+        }
+
+        let Some(hand) = died.killer else {
+            continue;
+        };
+
+        let Ok((mut hand_animation, mut hand_velocity)) = hands_query.get_mut(hand) else {
+            continue;
+        };
+
+        hand_animation.play("grab");
+        ev_sfx.send(PlaySfx::following(SfxCue::Grab, hand));
+        hand_velocity.linvel = Vec2::new(0.0, -RETREAT_SPEED);
+
+        commands.entity(hand).insert((
+            Grabbing {
+                window: Timer::from_seconds(GRAB_WINDOW_SECONDS, TimerMode::Once),
+                escape: 0.0,
+            },
+            FollowedByCamera::default(),
+        ));
+
+        //carried by the hand, physics would only fight it
+        commands
+            .entity(died.entity)
+            .remove::<FollowedByCamera>()
+            .insert((Grabbed { hand }, RigidBodyDisabled, ColliderDisabled));
+
+        spawn_struggle_prompt(&mut commands, &asset_server);
+    }
+}
+
+fn spawn_struggle_prompt(commands: &mut Commands, asset_server: &AssetServer) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    size: Size::new(Val::Percent(100.0), Val::Auto),
+                    position: UiRect {
+                        bottom: Val::Percent(20.0),
+                        ..default()
+                    },
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            StrugglePrompt,
+            Name::new("Struggle prompt"),
+            GameEntity,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Mash jump or shoot to break free!",
+                TextStyle {
+                    font: asset_server.load("century-gothic/gothic_bold.ttf"),
+                    font_size: 35.0,
+                    color: Color::WHITE,
+                },
+            ));
+
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Px(300.0), Val::Px(12.0)),
+                        margin: UiRect::top(Val::Px(8.0)),
+                        ..default()
+                    },
+                    background_color: BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.5)),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        NodeBundle {
+                            style: Style {
+                                size: Size::new(Val::Percent(0.0), Val::Percent(100.0)),
+                                ..default()
+                            },
+                            background_color: BackgroundColor(Color::WHITE),
+                            ..default()
+                        },
+                        StruggleBarFill,
+                    ));
+                });
+        });
+}
+
+//only the player fires projectiles, so any new one is a shot into the hand
+pub(super) fn struggle(
+    mut hands_query: Query<&mut Grabbing>,
+    actions: ActionInput,
+    new_shots: Query<(), Added<Projectile>>,
+    mut ev_damage: EventReader<Damage>,
+    time: Res<Time>,
+) {
+    for damage in ev_damage.iter() {
+        if let Ok(mut grabbing) = hands_query.get_mut(damage.target) {
+            grabbing.escape += HIT_PROGRESS;
+        }
+    }
+
+    for mut grabbing in hands_query.iter_mut() {
+        grabbing.window.tick(time.delta());
+
+        let mut progress = -PROGRESS_DECAY * time.delta_seconds();
+
+        if actions.just_pressed(Action::Jump) {
+            progress += MASH_PROGRESS;
+        }
+
+        if !new_shots.is_empty() {
+            progress += SHOT_PROGRESS;
+        }
+
+        grabbing.escape = (grabbing.escape + progress).clamp(0.0, 1.0);
+    }
+}
+
+pub(super) fn hold_grabbed_target(
+    mut grabbed_query: Query<(&Grabbed, &mut Transform)>,
+    hands_query: Query<&Children, With<Grabbing>>,
+    grab_hitbox_query: Query<&GlobalTransform, With<GrabHitbox>>,
+) {
+    for (grabbed, mut transform) in grabbed_query.iter_mut() {
+        let Ok(hand_children) = hands_query.get(grabbed.hand) else {
+            continue;
+        };
+
+        let Some(grab_hitbox) = grab_hitbox_query.iter_many(hand_children).next() else {
+            continue;
+        };
+
+        let z = transform.translation.z;
+        transform.translation = grab_hitbox.translation().truncate().extend(z);
+    }
+}
+
+//the player gets away if they struggled hard enough or the hand got blown up,
+//otherwise they're dragged off once the window runs out
+pub(super) fn resolve_grab(
+    mut commands: Commands,
+    mut grabbed_query: Query<(Entity, &Grabbed, &mut Health, &mut Velocity), Without<Arms>>,
+    mut hands_query: Query<(&Grabbing, &mut SpriteAnimation, &mut Velocity, &Children), With<Arms>>,
+    grab_hitbox_query: Query<Entity, With<GrabHitbox>>,
+    prompt_query: Query<Entity, With<StrugglePrompt>>,
+    mut ev_player_died: EventWriter<PlayerDied>,
+) {
+    for (target, grabbed, mut health, mut velocity) in grabbed_query.iter_mut() {
+        let escaped = match hands_query.get_mut(grabbed.hand) {
+            Err(_) => true,
+            Ok((grabbing, mut hand_animation, mut hand_velocity, hand_children)) => {
+                if grabbing.escape >= 1.0 {
+                    //lets go and drops away
+                    hand_animation.play("rise");
+                    hand_velocity.linvel = Vec2::new(0.0, -RETREAT_SPEED * 3.0);
+                    commands
+                        .entity(grabbed.hand)
+                        .remove::<(Grabbing, FollowedByCamera)>()
+                        .insert(Released);
+
+                    for grab_hitbox in grab_hitbox_query.iter_many(hand_children) {
+                        commands.entity(grab_hitbox).remove::<HurtsPlayer>();
+                    }
+
+                    true
+                } else if grabbing.window.finished() {
+                    //the camera stays on the hand as it takes the player away
+                    commands.entity(grabbed.hand).remove::<Grabbing>();
+                    commands.entity(target).despawn_recursive();
+                    ev_player_died.send(PlayerDied);
+
+                    false
+                } else {
+                    continue;
+                }
+            }
+        };
+
+        if escaped {
+            health.revive(ESCAPE_HEALTH);
+            velocity.linvel = Vec2::new(0.0, ESCAPE_LAUNCH_SPEED);
+
+            commands
+                .entity(target)
+                .remove::<(Grabbed, RigidBodyDisabled, ColliderDisabled)>()
+                .insert(FollowedByCamera::default());
+        }
+
+        for prompt in prompt_query.iter() {
+            commands.entity(prompt).despawn_recursive();
+        }
+    }
+}
+
+pub(super) fn update_struggle_bar(
+    hands_query: Query<&Grabbing>,
+    mut fill_query: Query<&mut Style, With<StruggleBarFill>>,
+) {
+    let (Ok(grabbing), Ok(mut fill)) = (hands_query.get_single(), fill_query.get_single_mut())
+    else {
+        return;
+    };
+
+    fill.size.width = Val::Percent(grabbing.escape * 100.0);
+}
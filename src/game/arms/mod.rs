@@ -0,0 +1,260 @@
+mod grab;
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::{Collider, GravityScale, RigidBody, Sensor, Velocity};
+use rand::*;
+
+use crate::{
+    game::{
+        animation::{AnimationSheet, SpriteAnimation},
+        difficulty::DifficultyDirector,
+        enemy::{Bestiary, Enemy, HurtsPlayer},
+        health::Health,
+        platform::chunk::EnemySpawnPoint,
+        seed::RunRng,
+        weapon::RocketTarget,
+    },
+    game_audio::{PlaySfx, SfxCue},
+    AppState,
+};
+
+use super::{GameEntity, PausedState};
+
+pub use self::grab::Grabbed;
+
+use self::grab::{
+    grab_target, hold_grabbed_target, resolve_grab, struggle, update_struggle_bar, Grabbing,
+    Released,
+};
+
+#[derive(Component)]
+pub struct Arms;
+
+#[derive(Component)]
+struct GrabHitbox;
+
+//spawn points further below the target than this are ignored
+const SPAWN_POINT_RANGE: f32 = 1500.0;
+
+//arms that missed keep going, this far above or below the target they're dropped
+const STRAY_DISTANCE: f32 = 3000.0;
+
+#[derive(Resource)]
+struct SpawnArmTimer(Timer);
+
+#[derive(Resource)]
+struct ArmsSheet(Handle<AnimationSheet>);
+
+fn load_arms_sheet(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(ArmsSheet(asset_server.load("enemies/arms.anim.ron")));
+}
+
+fn tick_spawn_arm_timer(
+    mut spawn_arm_timer: ResMut<SpawnArmTimer>,
+    director: Res<DifficultyDirector>,
+    time: Res<Time>,
+) {
+    let interval = Duration::from_secs_f32(director.spawn_interval);
+
+    if spawn_arm_timer.0.duration() != interval {
+        spawn_arm_timer.0.set_duration(interval);
+    }
+
+    spawn_arm_timer.0.tick(time.delta());
+}
+
+fn arm_spawn_due(spawn_arm_timer: Res<SpawnArmTimer>) -> bool {
+    spawn_arm_timer.0.just_finished()
+}
+
+//a released hand is on its way out, it doesn't take up a place
+fn below_arm_cap(
+    arms: Query<(), (With<Arms>, Without<Released>)>,
+    director: Res<DifficultyDirector>,
+) -> bool {
+    arms.iter().len() < director.max_arms
+}
+
+fn spawn_arms(
+    mut commands: Commands,
+    arms_sheet: Res<ArmsSheet>,
+    bestiary: Bestiary,
+    director: Res<DifficultyDirector>,
+    target: Query<&Transform, With<ArmsTarget>>,
+    spawn_points: Query<&GlobalTransform, With<EnemySpawnPoint>>,
+    mut run_rng: ResMut<RunRng>,
+) {
+    let random_gen = &mut run_rng.arms;
+
+    let Some(definitions) = bestiary.get() else {
+        return;
+    };
+    let definition = definitions.arms;
+
+    let Ok(target_transform) = target.get_single() else {
+      return;
+    };
+    let random_position = target_transform.translation
+        + Vec3::new(random_gen.gen_range(-150.0..150.0), -1200.0, 0.0);
+
+    //level chunks can say where arms come from, the closest one below wins
+    let spawn_position = spawn_points
+        .iter()
+        .map(|spawn_point| spawn_point.translation())
+        .filter(|spawn_point| {
+            let below = target_transform.translation.y - spawn_point.y;
+            below > 0.0 && below <= SPAWN_POINT_RANGE
+        })
+        .min_by(|a, b| {
+            a.distance(target_transform.translation)
+                .total_cmp(&b.distance(target_transform.translation))
+        })
+        .unwrap_or(random_position);
+
+    commands
+        .spawn((
+            Arms,
+            Enemy,
+            Health::new(definition.health, 0.0),
+            SpriteSheetBundle {
+                sprite: TextureAtlasSprite::new(0),
+                transform: Transform {
+                    translation: spawn_position,
+                    scale: Vec3::new(0.15, 0.15, 0.15),
+                    ..default()
+                },
+                ..default()
+            },
+            SpriteAnimation::new(arms_sheet.0.clone(), "rise"),
+            Name::new("Arm"),
+            RigidBody::KinematicVelocityBased,
+            GravityScale(0.0),
+            Velocity {
+                linvel: Vec2::new(0.0, director.rise_speed),
+                ..default()
+            },
+            GameEntity,
+        ))
+        .with_children(|parent| {
+            let mut grab_hitbox = parent.spawn((
+                SpatialBundle {
+                    transform: Transform {
+                        translation: Vec3::new(-10.0, 225.0, 0.1),
+                        ..default()
+                    },
+                    ..default()
+                },
+                Sensor,
+                Collider::cuboid(150.0, 150.0),
+                GrabHitbox,
+            ));
+
+            if let Some(contact) = definition.contact {
+                grab_hitbox.insert(HurtsPlayer {
+                    contact,
+                    despawn_on_hit: false,
+                });
+            }
+
+            parent.spawn((
+                SpatialBundle {
+                    transform: Transform {
+                        translation: Vec3::new(-10.0, 225.0, 0.1),
+                        ..default()
+                    },
+                    ..default()
+                },
+                Sensor,
+                Collider::cuboid(200.0, 300.0),
+                RocketTarget,
+            ));
+        });
+}
+
+#[allow(clippy::type_complexity)]
+fn chase_target(
+    target_query: Query<&Transform, (With<ArmsTarget>, Without<Arms>)>,
+    mut hands_query: Query<
+        (&Transform, &mut Velocity, &mut SpriteAnimation),
+        (
+            With<Arms>,
+            Without<ArmsTarget>,
+            Without<Grabbing>,
+            Without<Released>,
+        ),
+    >,
+    director: Res<DifficultyDirector>,
+) {
+    let Ok(target) = target_query.get_single() else {
+        return;
+    };
+
+    for (hand_transform, mut hand_velocity, mut hand_animation) in hands_query.iter_mut() {
+        if (hand_transform.translation.y - target.translation.y).abs() < 550.0
+            && (hand_transform.translation.x - target.translation.x).abs() < 150.0
+        {
+            hand_velocity.linvel = Vec2::new(0.0, director.chase_speed);
+            hand_animation.play("reach");
+        }
+    }
+}
+
+//heard coming up from below before they're on screen
+fn arm_rise_sounds(new_arms: Query<Entity, Added<Arms>>, mut ev_sfx: EventWriter<PlaySfx>) {
+    for arm in new_arms.iter() {
+        ev_sfx.send(PlaySfx::following(SfxCue::ArmRise, arm));
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn despawn_stray_arms(
+    target_query: Query<&Transform, (With<ArmsTarget>, Without<Arms>)>,
+    hands_query: Query<(Entity, &Transform), (With<Arms>, Without<Grabbing>)>,
+    mut commands: Commands,
+) {
+    let Ok(target) = target_query.get_single() else {
+        return;
+    };
+
+    for (hand, hand_transform) in hands_query.iter() {
+        if (hand_transform.translation.y - target.translation.y).abs() > STRAY_DISTANCE {
+            commands.entity(hand).despawn_recursive();
+        }
+    }
+}
+
+pub struct ArmsPlugin;
+
+#[derive(Component)]
+pub struct ArmsTarget;
+
+impl Plugin for ArmsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(SpawnArmTimer(Timer::new(
+            Duration::from_secs(2),
+            TimerMode::Repeating,
+        )))
+        .add_startup_system(load_arms_sheet)
+        .add_systems(
+            (
+                chase_target,
+                grab_target,
+                struggle.after(grab_target),
+                resolve_grab.after(struggle),
+                hold_grabbed_target.after(resolve_grab),
+                update_struggle_bar.after(struggle),
+                despawn_stray_arms,
+                arm_rise_sounds,
+                tick_spawn_arm_timer,
+                spawn_arms
+                    .after(tick_spawn_arm_timer)
+                    .run_if(arm_spawn_due)
+                    .run_if(below_arm_cap),
+            )
+                .in_set(OnUpdate(AppState::InGame))
+                .in_set(OnUpdate(PausedState::Playing)),
+        );
+    }
+}
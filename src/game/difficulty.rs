@@ -0,0 +1,204 @@
+use bevy::{prelude::*, reflect::TypeUuid};
+use serde::Deserialize;
+
+use crate::{utils::ron_asset::RonAssetPlugin, AppState};
+
+use super::{
+    platform::{PlatformLayout, PlatformLayoutCurve},
+    score::Score,
+    PausedState,
+};
+
+//picked on the main menu, the curve for each is in assets/difficulty
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+        }
+    }
+
+    pub fn next(&self) -> Difficulty {
+        match self {
+            Difficulty::Easy => Difficulty::Normal,
+            Difficulty::Normal => Difficulty::Hard,
+            Difficulty::Hard => Difficulty::Easy,
+        }
+    }
+}
+
+//a value that goes from start to end as the run gets harder
+#[derive(Deserialize, Clone, Copy, Debug)]
+struct Ramp {
+    start: f32,
+    end: f32,
+}
+
+impl Ramp {
+    fn at(&self, progress: f32) -> f32 {
+        self.start + (self.end - self.start) * progress
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+struct DifficultyCurve {
+    //pixels climbed and seconds survived until the enemies are at their end values,
+    //whichever of the two is further along sets the pace. platform spacing only goes
+    //by height, so a seed builds the same level however fast it's climbed
+    full_height: f32,
+    full_seconds: f32,
+    //seconds between arms
+    spawn_interval: Ramp,
+    //how fast arms come up from below, and once they've spotted the player
+    rise_speed: Ramp,
+    chase_speed: Ramp,
+    //rounded to the nearest whole arm
+    max_arms: Ramp,
+    //low and high ends of the ranges in PlatformLayout
+    platform_gap: (Ramp, Ramp),
+    platform_rise: (Ramp, Ramp),
+}
+
+impl DifficultyCurve {
+    fn progress(&self, score: &Score) -> f32 {
+        let by_height = score.max_height / self.full_height;
+        let by_time = score.run_time / self.full_seconds;
+        by_height.max(by_time).clamp(0.0, 1.0)
+    }
+
+    fn layout(&self) -> PlatformLayoutCurve {
+        let at = |progress: f32| PlatformLayout {
+            gap: (
+                self.platform_gap.0.at(progress),
+                self.platform_gap.1.at(progress),
+            ),
+            rise: (
+                self.platform_rise.0.at(progress),
+                self.platform_rise.1.at(progress),
+            ),
+        };
+
+        PlatformLayoutCurve {
+            bottom: at(0.0),
+            top: at(1.0),
+            full_height: self.full_height,
+        }
+    }
+}
+
+#[derive(Deserialize, TypeUuid, Debug)]
+#[uuid = "c2d7a9e4-3b61-4f0a-8e5d-7a1b9c4f2e83"]
+struct DifficultyPresets {
+    easy: DifficultyCurve,
+    normal: DifficultyCurve,
+    hard: DifficultyCurve,
+}
+
+impl DifficultyPresets {
+    fn get(&self, difficulty: Difficulty) -> &DifficultyCurve {
+        match difficulty {
+            Difficulty::Easy => &self.easy,
+            Difficulty::Normal => &self.normal,
+            Difficulty::Hard => &self.hard,
+        }
+    }
+}
+
+#[derive(Resource)]
+struct DifficultyPresetsHandle(Handle<DifficultyPresets>);
+
+//where the run's difficulty is at right now, everything that scales reads it from here.
+//the defaults are what a run gets until the presets file is in
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub struct DifficultyDirector {
+    pub spawn_interval: f32,
+    pub rise_speed: f32,
+    pub chase_speed: f32,
+    pub max_arms: usize,
+}
+
+impl Default for DifficultyDirector {
+    fn default() -> Self {
+        DifficultyDirector {
+            spawn_interval: 2.0,
+            rise_speed: 300.0,
+            chase_speed: 600.0,
+            max_arms: 3,
+        }
+    }
+}
+
+fn load_difficulty_presets(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(DifficultyPresetsHandle(
+        asset_server.load("difficulty/presets.difficulty.ron"),
+    ));
+}
+
+fn reset_director(mut commands: Commands) {
+    commands.insert_resource(DifficultyDirector::default());
+}
+
+fn direct_difficulty(
+    difficulty: Res<Difficulty>,
+    handle: Res<DifficultyPresetsHandle>,
+    presets: Res<Assets<DifficultyPresets>>,
+    score: Res<Score>,
+    mut director: ResMut<DifficultyDirector>,
+) {
+    let Some(presets) = presets.get(&handle.0) else {
+        return;
+    };
+
+    let curve = presets.get(*difficulty);
+    let progress = curve.progress(&score);
+
+    director.set_if_neq(DifficultyDirector {
+        spawn_interval: curve.spawn_interval.at(progress).max(0.1),
+        rise_speed: curve.rise_speed.at(progress),
+        chase_speed: curve.chase_speed.at(progress),
+        max_arms: curve.max_arms.at(progress).round().max(1.0) as usize,
+    });
+}
+
+//only depends on the difficulty and the presets, so it's ready before the run starts
+fn shape_platform_layout(
+    difficulty: Res<Difficulty>,
+    handle: Res<DifficultyPresetsHandle>,
+    presets: Res<Assets<DifficultyPresets>>,
+    mut layout_curve: ResMut<PlatformLayoutCurve>,
+) {
+    let Some(presets) = presets.get(&handle.0) else {
+        return;
+    };
+
+    layout_curve.set_if_neq(presets.get(*difficulty).layout());
+}
+
+pub struct DifficultyPlugin;
+
+impl Plugin for DifficultyPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugin(RonAssetPlugin::<DifficultyPresets>::new(&[
+            "difficulty.ron",
+        ]))
+        .init_resource::<Difficulty>()
+        .init_resource::<DifficultyDirector>()
+        .add_startup_system(load_difficulty_presets)
+        .add_system(reset_director.in_schedule(OnEnter(AppState::InGame)))
+        .add_system(
+            direct_difficulty
+                .in_set(OnUpdate(AppState::InGame))
+                .in_set(OnUpdate(PausedState::Playing)),
+        )
+        .add_system(shape_platform_layout);
+    }
+}
@@ -0,0 +1,210 @@
+mod sprinkle_turret;
+mod whisk;
+
+use bevy::{ecs::system::SystemParam, prelude::*, reflect::TypeUuid};
+use bevy_rapier2d::prelude::*;
+use serde::Deserialize;
+
+use crate::{
+    game::{
+        arms::ArmsTarget,
+        health::{Damage, Died, Health},
+        score::Score,
+        weapon::RocketTargetDestroyed,
+    },
+    utils::ron_asset::RonAssetPlugin,
+    AppState,
+};
+
+use super::PausedState;
+
+//anything rockets can hurt, next to its Health. explosions hit whatever the
+//RocketTarget collider belongs to, so it goes on the enemy itself or one of its children
+#[derive(Component)]
+pub struct Enemy;
+
+//what touching something does to the player
+#[derive(Deserialize, Clone, Copy, Debug)]
+pub struct ContactDamage {
+    pub damage: u32,
+    //speed the player gets sent away at
+    pub knockback: f32,
+}
+
+#[derive(Deserialize, Clone, Copy, Debug)]
+pub struct EnemyDefinition {
+    pub health: u32,
+    pub contact: Option<ContactDamage>,
+}
+
+//assets/enemies/bestiary.enemies.ron
+#[derive(Deserialize, TypeUuid, Debug)]
+#[uuid = "5e3f1a9b-7c28-4d6e-a0b4-91f2c7d8e635"]
+pub struct EnemyDefinitions {
+    pub arms: EnemyDefinition,
+    pub whisk: EnemyDefinition,
+    pub sprinkle_turret: EnemyDefinition,
+    //what the turrets lob
+    pub sprinkle: ContactDamage,
+}
+
+#[derive(Resource)]
+struct EnemyDefinitionsHandle(Handle<EnemyDefinitions>);
+
+//the loaded definitions, nothing spawns until the file is in
+#[derive(SystemParam)]
+pub struct Bestiary<'w> {
+    handle: Res<'w, EnemyDefinitionsHandle>,
+    definitions: Res<'w, Assets<EnemyDefinitions>>,
+}
+
+impl<'w> Bestiary<'w> {
+    pub fn get(&self) -> Option<&EnemyDefinitions> {
+        self.definitions.get(&self.handle.0)
+    }
+}
+
+//the enemies that spawn by rule, arms are paced by the difficulty director instead
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+enum EnemyKind {
+    Whisk,
+    SprinkleTurret,
+}
+
+struct SpawnRule {
+    //none of this kind until the player has climbed this high
+    min_height: f32,
+    max_alive: usize,
+}
+
+impl EnemyKind {
+    fn spawn_rule(&self) -> SpawnRule {
+        match self {
+            EnemyKind::Whisk => SpawnRule {
+                min_height: 1500.0,
+                max_alive: 2,
+            },
+            EnemyKind::SprinkleTurret => SpawnRule {
+                min_height: 3000.0,
+                max_alive: 3,
+            },
+        }
+    }
+}
+
+#[derive(SystemParam)]
+struct EnemySpawner<'w, 's> {
+    enemies: Query<'w, 's, &'static EnemyKind>,
+    score: Res<'w, Score>,
+    bestiary: Bestiary<'w>,
+}
+
+impl<'w, 's> EnemySpawner<'w, 's> {
+    //the definitions if one more of this kind is allowed right now
+    fn allows(&self, kind: EnemyKind) -> Option<&EnemyDefinitions> {
+        let rule = kind.spawn_rule();
+        let alive = self.enemies.iter().filter(|other| **other == kind).count();
+
+        if self.score.max_height < rule.min_height || alive >= rule.max_alive {
+            return None;
+        }
+
+        self.bestiary.get()
+    }
+}
+
+//hurts the player on contact, and goes away too if it's a one-off like a sprinkle.
+//on a child collider the damage is dealt by the parent
+#[derive(Component)]
+pub struct HurtsPlayer {
+    pub contact: ContactDamage,
+    pub despawn_on_hit: bool,
+}
+
+//staying in something that hurts keeps hurting, the player's invulnerability after a hit spaces it out
+fn hurt_player(
+    mut ev_collision: EventReader<CollisionEvent>,
+    hurts_player: Query<(Entity, &HurtsPlayer, &GlobalTransform, Option<&Parent>)>,
+    target_query: Query<(Entity, &GlobalTransform, &Health), With<ArmsTarget>>,
+    rapier_context: Res<RapierContext>,
+    mut commands: Commands,
+    mut ev_damage: EventWriter<Damage>,
+) {
+    //sprinkles can hit a platform too, they're gone on whatever they touch first
+    for collision_event in ev_collision.iter() {
+        let CollisionEvent::Started(h1, h2, _event_flag) = collision_event else {
+            continue;
+        };
+
+        for hurting in [*h1, *h2] {
+            if hurts_player
+                .get(hurting)
+                .is_ok_and(|(_, hurts, _, _)| hurts.despawn_on_hit)
+            {
+                commands.entity(hurting).despawn_recursive();
+            }
+        }
+    }
+
+    let Ok((target, target_transform, health)) = target_query.get_single() else {
+        return;
+    };
+
+    if health.is_invulnerable() {
+        return;
+    }
+
+    for (hurting, hurts, transform, parent) in hurts_player.iter() {
+        if rapier_context.intersection_pair(hurting, target) != Some(true) {
+            continue;
+        }
+
+        let away = (target_transform.translation() - transform.translation())
+            .truncate()
+            .try_normalize()
+            .unwrap_or(Vec2::Y);
+
+        ev_damage.send(Damage {
+            target,
+            amount: hurts.contact.damage,
+            knockback: away * hurts.contact.knockback,
+            source: Some(parent.map_or(hurting, |parent| parent.get())),
+        });
+    }
+}
+
+fn kill_enemies(
+    mut ev_died: EventReader<Died>,
+    enemies: Query<(), With<Enemy>>,
+    mut commands: Commands,
+    mut ev_destroyed: EventWriter<RocketTargetDestroyed>,
+) {
+    for died in ev_died.iter() {
+        if enemies.contains(died.entity) {
+            commands.entity(died.entity).despawn_recursive();
+            ev_destroyed.send(RocketTargetDestroyed(died.entity));
+        }
+    }
+}
+
+fn load_enemy_definitions(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(EnemyDefinitionsHandle(
+        asset_server.load("enemies/bestiary.enemies.ron"),
+    ));
+}
+
+pub struct EnemyPlugin;
+
+impl Plugin for EnemyPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugin(RonAssetPlugin::<EnemyDefinitions>::new(&["enemies.ron"]))
+            .add_startup_system(load_enemy_definitions)
+            .add_plugin(whisk::WhiskPlugin)
+            .add_plugin(sprinkle_turret::SprinkleTurretPlugin)
+            .add_systems(
+                (hurt_player, kill_enemies)
+                    .in_set(OnUpdate(AppState::InGame))
+                    .in_set(OnUpdate(PausedState::Playing)),
+            );
+    }
+}
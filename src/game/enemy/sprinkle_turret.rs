@@ -0,0 +1,206 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use rand::Rng;
+
+use crate::{
+    game::{
+        arms::ArmsTarget,
+        health::Health,
+        platform::{Handcrafted, Platform, PLATFORM_HALF_SIZE},
+        seed::RunRng,
+        weapon::RocketTarget,
+        GameEntity, PausedState,
+    },
+    AppState,
+};
+
+use super::{ContactDamage, Enemy, EnemyKind, EnemySpawner, HurtsPlayer};
+
+//chance a new platform gets a turret, once they're allowed
+const TURRET_CHANCE: f64 = 0.15;
+
+const FIRE_INTERVAL_SECONDS: f32 = 2.5;
+
+//only lobs at the player when they're this close
+const RANGE: f32 = 900.0;
+
+//every sprinkle is in the air this long before reaching where the player was
+const FLIGHT_SECONDS: f32 = 1.2;
+
+//missed sprinkles fall forever otherwise
+const SPRINKLE_LIFETIME_SECONDS: f32 = 5.0;
+
+const SPRINKLE_COLORS: [Color; 5] = [
+    Color::rgb(1.0, 0.4, 0.6),
+    Color::rgb(0.5, 0.8, 1.0),
+    Color::rgb(1.0, 0.9, 0.4),
+    Color::rgb(0.6, 1.0, 0.6),
+    Color::rgb(0.8, 0.6, 1.0),
+];
+
+//sits off to the side of a platform so it doesn't cover a pickup
+#[derive(Component)]
+struct SprinkleTurret {
+    fire: Timer,
+    sprinkle: ContactDamage,
+}
+
+#[derive(Component)]
+struct Sprinkle {
+    lifetime: Timer,
+}
+
+//goes on generated platforms only, chunks say where their enemies go
+fn spawn_turrets(
+    new_platforms: Query<Entity, (Added<Platform>, Without<Handcrafted>)>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    spawner: EnemySpawner,
+    mut run_rng: ResMut<RunRng>,
+) {
+    let rand_gen = &mut run_rng.enemies;
+
+    for platform in new_platforms.iter() {
+        let Some(definitions) = spawner.allows(EnemyKind::SprinkleTurret) else {
+            continue;
+        };
+
+        if !rand_gen.gen_bool(TURRET_CHANCE) {
+            continue;
+        }
+
+        let definition = definitions.sprinkle_turret;
+
+        let mut fire = Timer::from_seconds(FIRE_INTERVAL_SECONDS, TimerMode::Repeating);
+        //so turrets that show up together don't fire together
+        fire.set_elapsed(fire.duration().mul_f32(rand_gen.gen()));
+
+        let turret = commands
+            .spawn((
+                SprinkleTurret {
+                    fire,
+                    sprinkle: definitions.sprinkle,
+                },
+                Enemy,
+                Health::new(definition.health, 0.0),
+                EnemyKind::SprinkleTurret,
+                SpriteBundle {
+                    texture: asset_server.load("enemies/sprinkle_turret.png"),
+                    transform: Transform::from_xyz(
+                        -PLATFORM_HALF_SIZE.x + 64.0,
+                        PLATFORM_HALF_SIZE.y + 48.0,
+                        0.5,
+                    ),
+                    ..default()
+                },
+                Name::new("Sprinkle turret"),
+            ))
+            .with_children(|parent| {
+                let mut hitbox = parent.spawn((
+                    SpatialBundle::default(),
+                    Sensor,
+                    Collider::cuboid(50.0, 45.0),
+                    RocketTarget,
+                ));
+
+                if let Some(contact) = definition.contact {
+                    hitbox.insert(HurtsPlayer {
+                        contact,
+                        despawn_on_hit: false,
+                    });
+                }
+            })
+            .id();
+
+        commands.entity(platform).push_children(&[turret]);
+    }
+}
+
+fn fire_turrets(
+    mut turrets: Query<(&GlobalTransform, &mut SprinkleTurret)>,
+    target: Query<&Transform, With<ArmsTarget>>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    rapier_config: Res<RapierConfiguration>,
+    mut run_rng: ResMut<RunRng>,
+    time: Res<Time>,
+) {
+    let Ok(target_transform) = target.get_single() else {
+        return;
+    };
+
+    let rand_gen = &mut run_rng.enemies;
+
+    for (transform, mut turret) in turrets.iter_mut() {
+        turret.fire.tick(time.delta());
+
+        let muzzle = transform.translation().truncate() + Vec2::new(0.0, 50.0);
+        let offset = target_transform.translation.truncate() - muzzle;
+
+        if !turret.fire.just_finished() || offset.length() > RANGE {
+            continue;
+        }
+
+        //the throw that lands on the player after FLIGHT_SECONDS, gravity and all
+        let velocity = offset / FLIGHT_SECONDS - rapier_config.gravity * FLIGHT_SECONDS / 2.0;
+
+        commands.spawn((
+            Sprinkle {
+                lifetime: Timer::from_seconds(SPRINKLE_LIFETIME_SECONDS, TimerMode::Once),
+            },
+            SpriteBundle {
+                texture: asset_server.load("enemies/sprinkle.png"),
+                sprite: Sprite {
+                    color: SPRINKLE_COLORS[rand_gen.gen_range(0..SPRINKLE_COLORS.len())],
+                    ..default()
+                },
+                transform: Transform {
+                    translation: muzzle.extend(1.0),
+                    rotation: Quat::from_rotation_z(rand_gen.gen_range(0.0..std::f32::consts::TAU)),
+                    ..default()
+                },
+                ..default()
+            },
+            RigidBody::Dynamic,
+            Velocity {
+                linvel: velocity,
+                angvel: rand_gen.gen_range(-10.0..10.0),
+            },
+            Collider::capsule_y(12.0, 6.0),
+            Sensor,
+            ActiveEvents::COLLISION_EVENTS,
+            HurtsPlayer {
+                contact: turret.sprinkle,
+                despawn_on_hit: true,
+            },
+            Name::new("Sprinkle"),
+            GameEntity,
+        ));
+    }
+}
+
+fn expire_sprinkles(
+    mut sprinkles: Query<(Entity, &mut Sprinkle)>,
+    mut commands: Commands,
+    time: Res<Time>,
+) {
+    for (entity, mut sprinkle) in sprinkles.iter_mut() {
+        sprinkle.lifetime.tick(time.delta());
+
+        if sprinkle.lifetime.finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+pub(super) struct SprinkleTurretPlugin;
+
+impl Plugin for SprinkleTurretPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            (spawn_turrets, fire_turrets, expire_sprinkles)
+                .in_set(OnUpdate(AppState::InGame))
+                .in_set(OnUpdate(PausedState::Playing)),
+        );
+    }
+}
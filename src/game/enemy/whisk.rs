@@ -0,0 +1,159 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use rand::Rng;
+
+use crate::{
+    game::{
+        arms::ArmsTarget, health::Health, seed::RunRng, weapon::RocketTarget, GameEntity,
+        PausedState,
+    },
+    AppState,
+};
+
+use super::{Enemy, EnemyKind, EnemySpawner, HurtsPlayer};
+
+const SPAWN_INTERVAL_SECONDS: f32 = 5.0;
+
+//comes in from just past the edge of the view, somewhere above the player
+const SPAWN_DISTANCE: f32 = 1100.0;
+
+const SPEED: f32 = 350.0;
+
+//how hard it steers toward the player's height, per pixel off
+const SWOOP_PULL: f32 = 1.5;
+const MAX_SWOOP_SPEED: f32 = 250.0;
+
+//gives up and leaves once it's overshot by this much
+const GIVE_UP_DISTANCE: f32 = 1400.0;
+
+//flies across the screen at the player, dipping to their height on the way
+#[derive(Component)]
+struct Whisk {
+    //-1 flying left, 1 flying right
+    direction: f32,
+}
+
+#[derive(Resource)]
+struct SpawnWhiskTimer(Timer);
+
+impl Default for SpawnWhiskTimer {
+    fn default() -> Self {
+        SpawnWhiskTimer(Timer::from_seconds(
+            SPAWN_INTERVAL_SECONDS,
+            TimerMode::Repeating,
+        ))
+    }
+}
+
+//every run starts a whole interval away from the first whisk
+fn reset_spawn_whisk_timer(mut commands: Commands) {
+    commands.insert_resource(SpawnWhiskTimer::default());
+}
+
+fn spawn_whisks(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut spawn_whisk_timer: ResMut<SpawnWhiskTimer>,
+    time: Res<Time>,
+    target: Query<&Transform, With<ArmsTarget>>,
+    spawner: EnemySpawner,
+    mut run_rng: ResMut<RunRng>,
+) {
+    spawn_whisk_timer.0.tick(time.delta());
+
+    if !spawn_whisk_timer.0.just_finished() {
+        return;
+    }
+
+    let Some(definitions) = spawner.allows(EnemyKind::Whisk) else {
+        return;
+    };
+    let definition = definitions.whisk;
+
+    let Ok(target_transform) = target.get_single() else {
+        return;
+    };
+
+    let rand_gen = &mut run_rng.enemies;
+
+    let direction = if rand_gen.gen_bool(0.5) { -1.0 } else { 1.0 };
+    let position = target_transform.translation
+        + Vec3::new(
+            -direction * SPAWN_DISTANCE,
+            rand_gen.gen_range(100.0..400.0),
+            0.0,
+        );
+
+    let mut whisk = commands.spawn((
+        Whisk { direction },
+        Enemy,
+        Health::new(definition.health, 0.0),
+        EnemyKind::Whisk,
+        SpriteBundle {
+            texture: asset_server.load("enemies/whisk.png"),
+            sprite: Sprite {
+                //the sprite faces left
+                flip_x: direction > 0.0,
+                ..default()
+            },
+            transform: Transform {
+                translation: position,
+                scale: Vec3::new(0.8, 0.8, 1.0),
+                ..default()
+            },
+            ..default()
+        },
+        RigidBody::KinematicVelocityBased,
+        Velocity::default(),
+        Collider::ball(50.0),
+        Sensor,
+        RocketTarget,
+        Name::new("Whisk"),
+        GameEntity,
+    ));
+
+    if let Some(contact) = definition.contact {
+        whisk.insert(HurtsPlayer {
+            contact,
+            despawn_on_hit: false,
+        });
+    }
+}
+
+fn fly_whisks(
+    mut whisks: Query<(Entity, &Transform, &mut Velocity, &Whisk), Without<ArmsTarget>>,
+    target: Query<&Transform, With<ArmsTarget>>,
+    mut commands: Commands,
+) {
+    let Ok(target_transform) = target.get_single() else {
+        return;
+    };
+
+    for (entity, transform, mut velocity, whisk) in whisks.iter_mut() {
+        let offset = target_transform.translation - transform.translation;
+
+        if -offset.x * whisk.direction > GIVE_UP_DISTANCE {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        velocity.linvel = Vec2::new(
+            whisk.direction * SPEED,
+            (offset.y * SWOOP_PULL).clamp(-MAX_SWOOP_SPEED, MAX_SWOOP_SPEED),
+        );
+    }
+}
+
+pub(super) struct WhiskPlugin;
+
+impl Plugin for WhiskPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpawnWhiskTimer>()
+            .add_system(reset_spawn_whisk_timer.in_schedule(OnEnter(AppState::InGame)))
+            .add_systems(
+                (spawn_whisks, fly_whisks)
+                    .in_set(OnUpdate(AppState::InGame))
+                    .in_set(OnUpdate(PausedState::Playing)),
+            );
+    }
+}
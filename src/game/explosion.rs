@@ -0,0 +1,143 @@
+use bevy::{prelude::*, utils::HashSet};
+use bevy_rapier2d::prelude::*;
+use serde::Deserialize;
+
+use crate::{
+    game::{health::Damage, weapon::RocketTarget},
+    game_audio::{PlaySfx, SfxCue},
+    AppState,
+};
+
+use super::PausedState;
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct Explosion {
+    pub radius: f32,
+    //velocity in px/s given to a body right at the center, fading to 0 at the edge
+    pub strength: f32,
+    //taken off the health of every enemy in the radius
+    #[serde(default = "default_damage")]
+    pub damage: u32,
+}
+
+fn default_damage() -> u32 {
+    1
+}
+
+pub struct ExplosionEvent {
+    pub position: Vec2,
+    pub explosion: Explosion,
+}
+
+fn falloff(distance: f32, radius: f32) -> f32 {
+    (1.0 - distance / radius).max(0.0)
+}
+
+#[allow(clippy::type_complexity)]
+fn push_bodies(
+    mut ev_explosion: EventReader<ExplosionEvent>,
+    mut bodies: Query<
+        (
+            Entity,
+            &GlobalTransform,
+            &RigidBody,
+            &ReadMassProperties,
+            Option<&mut ExternalImpulse>,
+        ),
+        Without<RigidBodyDisabled>,
+    >,
+    mut commands: Commands,
+) {
+    for explosion_event in ev_explosion.iter() {
+        let Explosion {
+            radius, strength, ..
+        } = explosion_event.explosion;
+
+        for (entity, transform, rigid_body, mass_properties, impulse) in bodies.iter_mut() {
+            if *rigid_body != RigidBody::Dynamic {
+                continue;
+            }
+
+            let offset = transform.translation().truncate() - explosion_event.position;
+            let amount = falloff(offset.length(), radius);
+
+            if amount <= 0.0 {
+                continue;
+            }
+
+            //straight up if it went off right on top of the body
+            let direction = offset.try_normalize().unwrap_or(Vec2::Y);
+
+            //scaled by mass so every body gets the same kick
+            let kick = direction * strength * amount * mass_properties.0.mass;
+
+            if let Some(mut impulse) = impulse {
+                impulse.impulse += kick;
+            } else {
+                commands.entity(entity).insert(ExternalImpulse {
+                    impulse: kick,
+                    ..default()
+                });
+            }
+        }
+    }
+}
+
+fn hit_targets(
+    mut ev_explosion: EventReader<ExplosionEvent>,
+    rocket_targets: Query<(Entity, &GlobalTransform, Option<&Parent>), With<RocketTarget>>,
+    mut ev_damage: EventWriter<Damage>,
+) {
+    for explosion_event in ev_explosion.iter() {
+        //an enemy with a few target colliders still only gets hurt once
+        let mut hit = HashSet::new();
+
+        for (target, transform, parent_option) in rocket_targets.iter() {
+            let distance = transform
+                .translation()
+                .truncate()
+                .distance(explosion_event.position);
+
+            if distance > explosion_event.explosion.radius {
+                continue;
+            }
+
+            hit.insert(if let Some(parent) = parent_option {
+                **parent
+            } else {
+                target
+            });
+        }
+
+        //bodies already got pushed around in push_bodies
+        for target in hit {
+            ev_damage.send(Damage {
+                target,
+                amount: explosion_event.explosion.damage,
+                knockback: Vec2::ZERO,
+                source: None,
+            });
+        }
+    }
+}
+
+fn explosion_sounds(
+    mut ev_explosion: EventReader<ExplosionEvent>,
+    mut ev_sfx: EventWriter<PlaySfx>,
+) {
+    for explosion_event in ev_explosion.iter() {
+        ev_sfx.send(PlaySfx::at(SfxCue::Explode, explosion_event.position));
+    }
+}
+
+pub struct ExplosionPlugin;
+
+impl Plugin for ExplosionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<ExplosionEvent>().add_systems(
+            (push_bodies, hit_targets, explosion_sounds)
+                .in_set(OnUpdate(AppState::InGame))
+                .in_set(OnUpdate(PausedState::Playing)),
+        );
+    }
+}
@@ -0,0 +1,256 @@
+use bevy::prelude::*;
+
+use crate::{
+    high_scores::{format_run_time, HighScoreEntry, HighScores},
+    utils::interact_button::{self, HoverButton},
+    AppState, PIXELS_PER_METER,
+};
+
+use super::{
+    difficulty::Difficulty, platform::chunk::CustomLayout, player::PlayerDied, score::Score,
+    seed::RunSeed, PausedState,
+};
+
+//time to watch the arm drag the player away before the results come up
+const GAME_OVER_DELAY_SECONDS: f32 = 1.5;
+const TOAST_SECONDS: f32 = 3.0;
+
+#[derive(Component)]
+struct GameOverMenu;
+
+#[derive(Component)]
+struct RecordToast(Timer);
+
+#[derive(Component)]
+struct RetryButton;
+
+impl HoverButton for RetryButton {
+    fn on_click(commands: &mut Commands) {
+        commands.insert_resource(NextState(Some(AppState::InGame)));
+    }
+}
+
+#[derive(Component)]
+struct MainMenuButton;
+
+impl HoverButton for MainMenuButton {
+    fn on_click(commands: &mut Commands) {
+        commands.insert_resource(NextState(Some(AppState::MainMenu)));
+    }
+}
+
+#[derive(Resource)]
+struct GameOverDelay(Timer);
+
+fn end_run_after_death(
+    mut commands: Commands,
+    mut ev_player_died: EventReader<PlayerDied>,
+    delay: Option<ResMut<GameOverDelay>>,
+    time: Res<Time>,
+) {
+    if ev_player_died.iter().next().is_some() {
+        commands.insert_resource(GameOverDelay(Timer::from_seconds(
+            GAME_OVER_DELAY_SECONDS,
+            TimerMode::Once,
+        )));
+    }
+
+    let Some(mut delay) = delay else {
+        return;
+    };
+
+    if delay.0.tick(time.delta()).finished() {
+        commands.insert_resource(NextState(Some(AppState::GameOver)));
+        commands.remove_resource::<GameOverDelay>();
+    }
+}
+
+//quitting to the menu while being dragged away shouldn't end the next run
+fn cancel_game_over_delay(mut commands: Commands) {
+    commands.remove_resource::<GameOverDelay>();
+}
+
+fn record_high_score(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    score: Res<Score>,
+    mut high_scores: ResMut<HighScores>,
+    custom_layout: Res<CustomLayout>,
+) {
+    //playtesting an editor layout isn't a real run
+    if custom_layout.0.is_some() {
+        return;
+    }
+
+    let rank = high_scores.insert(HighScoreEntry::new(
+        score.max_height,
+        score.enemies_destroyed,
+        score.run_time,
+    ));
+
+    if rank != Some(0) {
+        return;
+    }
+
+    commands.spawn((
+        TextBundle::from_section(
+            "New record!",
+            TextStyle {
+                font: asset_server.load("century-gothic/gothic_bold.ttf"),
+                font_size: 40.0,
+                color: Color::GOLD,
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                top: Val::Px(30.0),
+                ..default()
+            },
+            align_self: AlignSelf::Center,
+            ..default()
+        }),
+        RecordToast(Timer::from_seconds(TOAST_SECONDS, TimerMode::Once)),
+        GameOverMenu,
+    ));
+}
+
+fn fade_record_toast(
+    mut commands: Commands,
+    mut toast_query: Query<(Entity, &mut RecordToast, &mut Text)>,
+    time: Res<Time>,
+) {
+    for (entity, mut toast, mut text) in toast_query.iter_mut() {
+        toast.0.tick(time.delta());
+
+        if toast.0.finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        text.sections[0].style.color.set_a(toast.0.percent_left());
+    }
+}
+
+fn create_game_over_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    score: Res<Score>,
+    run_seed: Res<RunSeed>,
+    difficulty: Res<Difficulty>,
+) {
+    let font = asset_server.load("century-gothic/gothic.ttf");
+
+    commands
+        .spawn((
+            NodeBundle {
+                background_color: BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.5)),
+                style: Style {
+                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                    position_type: PositionType::Absolute,
+                    display: Display::Flex,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    flex_direction: FlexDirection::Column,
+                    gap: Size::height(Val::Px(10.0)),
+                    ..default()
+                },
+                ..default()
+            },
+            GameOverMenu,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Grabbed!",
+                TextStyle {
+                    font: font.clone(),
+                    font_size: 60.0,
+                    color: Color::WHITE,
+                },
+            ));
+
+            for line in [
+                format!("Height: {}m", (score.max_height / PIXELS_PER_METER) as i32),
+                format!("Platforms: {}", score.platforms_landed),
+                format!("Enemies destroyed: {}", score.enemies_destroyed),
+                format!("Time: {}", format_run_time(score.run_time)),
+                //the seed only builds the same level on the same difficulty
+                format!("Seed: {} ({})", run_seed.0, difficulty.label()),
+            ] {
+                parent.spawn(TextBundle::from_section(
+                    line,
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 30.0,
+                        color: Color::WHITE,
+                    },
+                ));
+            }
+
+            create_game_over_button(parent, &font, "retry", RetryButton);
+            create_game_over_button(parent, &font, "main menu", MainMenuButton);
+        });
+}
+
+fn create_game_over_button(
+    parent: &mut ChildBuilder,
+    font: &Handle<Font>,
+    label: &str,
+    bundle: impl Bundle,
+) {
+    parent
+        .spawn((
+            ButtonBundle {
+                background_color: BackgroundColor(Color::BLACK),
+                style: Style {
+                    padding: UiRect::all(Val::Px(15.0)),
+                    size: Size::width(Val::Px(220.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                ..default()
+            },
+            bundle,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                label,
+                TextStyle {
+                    font: font.clone(),
+                    color: Color::WHITE,
+                    font_size: 25.0,
+                },
+            ));
+        });
+}
+
+fn despawn_game_over_menu(mut commands: Commands, menu: Query<Entity, With<GameOverMenu>>) {
+    for menu_entity in menu.iter() {
+        commands.entity(menu_entity).despawn_recursive();
+    }
+}
+
+pub struct GameOverPlugin;
+
+impl Plugin for GameOverPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(
+            end_run_after_death
+                .in_set(OnUpdate(AppState::InGame))
+                .in_set(OnUpdate(PausedState::Playing)),
+        )
+        .add_system(cancel_game_over_delay.in_schedule(OnExit(AppState::InGame)))
+        .add_systems(
+            (create_game_over_menu, record_high_score).in_schedule(OnEnter(AppState::GameOver)),
+        )
+        .add_system(despawn_game_over_menu.in_schedule(OnExit(AppState::GameOver)))
+        .add_systems(
+            (
+                interact_button::interact_system::<RetryButton>,
+                interact_button::interact_system::<MainMenuButton>,
+                fade_record_toast,
+            )
+                .in_set(OnUpdate(AppState::GameOver)),
+        );
+    }
+}
@@ -0,0 +1,155 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::Velocity;
+
+use crate::AppState;
+
+use super::PausedState;
+
+//how long something stays tinted after getting hit
+const HIT_FLASH_SECONDS: f32 = 0.15;
+
+const HIT_FLASH_COLOR: Color = Color::rgb(1.0, 0.35, 0.35);
+
+//how fast the sprite blinks while invulnerable, in blinks per second
+const BLINK_RATE: f32 = 12.0;
+
+#[derive(Component)]
+pub struct Health {
+    pub current: u32,
+    pub max: u32,
+    //can't be hurt again until this runs out
+    invulnerable: Timer,
+    hit_flash: Timer,
+}
+
+impl Health {
+    pub fn new(max: u32, invulnerable_seconds: f32) -> Self {
+        let finished = |seconds: f32| {
+            let mut timer = Timer::from_seconds(seconds, TimerMode::Once);
+            timer.tick(timer.duration());
+            timer
+        };
+
+        Health {
+            current: max,
+            max,
+            invulnerable: finished(invulnerable_seconds),
+            hit_flash: finished(HIT_FLASH_SECONDS),
+        }
+    }
+
+    pub fn is_invulnerable(&self) -> bool {
+        !self.invulnerable.finished()
+    }
+
+    //back up with this much, and a moment to get clear
+    pub fn revive(&mut self, health: u32) {
+        self.current = health.min(self.max);
+
+        if !self.invulnerable.duration().is_zero() {
+            self.invulnerable.reset();
+        }
+    }
+}
+
+pub struct Damage {
+    pub target: Entity,
+    pub amount: u32,
+    //added straight onto the target's velocity
+    pub knockback: Vec2,
+    //whatever dealt it, if it was something in the world
+    pub source: Option<Entity>,
+}
+
+//sent once when health runs out, whoever owns the entity decides what dying means
+pub struct Died {
+    pub entity: Entity,
+    pub killer: Option<Entity>,
+}
+
+fn apply_damage(
+    mut ev_damage: EventReader<Damage>,
+    mut targets: Query<(&mut Health, Option<&mut Velocity>)>,
+    mut ev_died: EventWriter<Died>,
+) {
+    for damage in ev_damage.iter() {
+        let Ok((mut health, velocity)) = targets.get_mut(damage.target) else {
+            continue;
+        };
+
+        //already dead, waiting to be despawned
+        if health.current == 0 || health.is_invulnerable() {
+            continue;
+        }
+
+        health.current = health.current.saturating_sub(damage.amount);
+
+        health.hit_flash.reset();
+
+        //a zero length timer would still block the rest of this frame's hits
+        if !health.invulnerable.duration().is_zero() {
+            health.invulnerable.reset();
+        }
+
+        if let Some(mut velocity) = velocity {
+            velocity.linvel += damage.knockback;
+        }
+
+        if health.current == 0 {
+            ev_died.send(Died {
+                entity: damage.target,
+                killer: damage.source,
+            });
+        }
+    }
+}
+
+//red right after a hit, then blinking until it can be hurt again
+fn show_hits(
+    mut targets: Query<(
+        &mut Health,
+        Option<&mut Sprite>,
+        Option<&mut TextureAtlasSprite>,
+    )>,
+    time: Res<Time>,
+) {
+    for (mut health, sprite, atlas_sprite) in targets.iter_mut() {
+        if health.hit_flash.finished() && !health.is_invulnerable() {
+            continue;
+        }
+
+        health.hit_flash.tick(time.delta());
+        health.invulnerable.tick(time.delta());
+
+        let blink_off = health.is_invulnerable()
+            && (health.invulnerable.elapsed_secs() * BLINK_RATE) as u32 % 2 == 1;
+
+        let color = if !health.hit_flash.finished() {
+            HIT_FLASH_COLOR
+        } else if blink_off {
+            Color::rgba(1.0, 1.0, 1.0, 0.3)
+        } else {
+            Color::WHITE
+        };
+
+        if let Some(mut sprite) = sprite {
+            sprite.color = color;
+        }
+
+        if let Some(mut atlas_sprite) = atlas_sprite {
+            atlas_sprite.color = color;
+        }
+    }
+}
+
+pub struct HealthPlugin;
+
+impl Plugin for HealthPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<Damage>().add_event::<Died>().add_systems(
+            (apply_damage, show_hits.after(apply_damage))
+                .in_set(OnUpdate(AppState::InGame))
+                .in_set(OnUpdate(PausedState::Playing)),
+        );
+    }
+}
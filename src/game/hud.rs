@@ -0,0 +1,210 @@
+use bevy::prelude::*;
+
+use crate::{AppState, PIXELS_PER_METER};
+
+use super::{arms::ArmsTarget, health::Health, score::Score, weapon::HeldWeapon, GameEntity};
+
+#[derive(Component)]
+struct ScoreText;
+
+#[derive(Component)]
+struct HealthText;
+
+#[derive(Component)]
+struct AmmoText;
+
+#[derive(Component)]
+struct ReloadBar;
+
+#[derive(Component)]
+struct ReloadBarFill;
+
+fn create_hud(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let text_style = TextStyle {
+        font: asset_server.load("century-gothic/gothic_bold.ttf"),
+        font_size: 30.0,
+        color: Color::WHITE,
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        left: Val::Px(15.0),
+                        top: Val::Px(10.0),
+                        ..default()
+                    },
+                    display: Display::Flex,
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                ..default()
+            },
+            Name::new("Hud"),
+            GameEntity,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_sections([
+                    TextSection::new("", text_style.clone()),
+                    TextSection::new("\n", text_style.clone()),
+                    TextSection::new("", text_style.clone()),
+                    TextSection::new("\n", text_style.clone()),
+                    TextSection::new("", text_style.clone()),
+                ]),
+                ScoreText,
+            ));
+
+            parent.spawn((TextBundle::from_section("", text_style.clone()), HealthText));
+        });
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        left: Val::Px(15.0),
+                        bottom: Val::Px(10.0),
+                        ..default()
+                    },
+                    display: Display::Flex,
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                ..default()
+            },
+            Name::new("Weapon hud"),
+            GameEntity,
+        ))
+        .with_children(|parent| {
+            parent.spawn((TextBundle::from_section("", text_style), AmmoText));
+
+            parent
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            size: Size::new(Val::Px(200.0), Val::Px(8.0)),
+                            margin: UiRect::top(Val::Px(5.0)),
+                            ..default()
+                        },
+                        background_color: BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.5)),
+                        visibility: Visibility::Hidden,
+                        ..default()
+                    },
+                    ReloadBar,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        NodeBundle {
+                            style: Style {
+                                size: Size::new(Val::Percent(0.0), Val::Percent(100.0)),
+                                ..default()
+                            },
+                            background_color: BackgroundColor(Color::WHITE),
+                            ..default()
+                        },
+                        ReloadBarFill,
+                    ));
+                });
+        });
+}
+
+fn update_score_text(score: Res<Score>, mut score_text_query: Query<&mut Text, With<ScoreText>>) {
+    let Ok(mut text) = score_text_query.get_single_mut() else {
+        return;
+    };
+
+    if !score.is_changed() && !text.is_added() {
+        return;
+    }
+
+    text.sections[0].value = format!("Height: {}m", (score.max_height / PIXELS_PER_METER) as i32);
+    text.sections[2].value = format!("Platforms: {}", score.platforms_landed);
+    text.sections[4].value = format!("Enemies: {}", score.enemies_destroyed);
+}
+
+fn update_health_text(
+    player_query: Query<&Health, With<ArmsTarget>>,
+    mut health_text_query: Query<&mut Text, With<HealthText>>,
+) {
+    let Ok(mut text) = health_text_query.get_single_mut() else {
+        return;
+    };
+
+    //the player is gone once they've died
+    let (current, max) = player_query
+        .get_single()
+        .map_or((0, 0), |health| (health.current, health.max));
+
+    let value = if max > 0 {
+        format!("Health: {current} / {max}")
+    } else {
+        "Health: 0".to_string()
+    };
+
+    if text.sections[0].value != value {
+        text.sections[0].value = value;
+    }
+}
+
+fn update_ammo_text(
+    held_weapon: Res<HeldWeapon>,
+    mut ammo_text_query: Query<&mut Text, With<AmmoText>>,
+) {
+    let Ok(mut text) = ammo_text_query.get_single_mut() else {
+        return;
+    };
+
+    if !held_weapon.is_changed() && !text.is_added() {
+        return;
+    }
+
+    text.sections[0].value = match held_weapon.ammo {
+        Some((loaded, reserve)) => format!("{}: {loaded} / {reserve}", held_weapon.name),
+        None => held_weapon.name.clone(),
+    };
+}
+
+fn update_reload_bar(
+    held_weapon: Res<HeldWeapon>,
+    mut reload_bar_query: Query<&mut Visibility, With<ReloadBar>>,
+    mut reload_bar_fill_query: Query<&mut Style, With<ReloadBarFill>>,
+) {
+    if !held_weapon.is_changed() {
+        return;
+    }
+
+    let (Ok(mut visibility), Ok(mut fill)) = (
+        reload_bar_query.get_single_mut(),
+        reload_bar_fill_query.get_single_mut(),
+    ) else {
+        return;
+    };
+
+    *visibility = if held_weapon.reload_progress.is_some() {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    fill.size.width = Val::Percent(held_weapon.reload_progress.unwrap_or(0.0) * 100.0);
+}
+
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(create_hud.in_schedule(OnEnter(AppState::InGame)))
+            .add_systems(
+                (
+                    update_score_text,
+                    update_health_text,
+                    update_ammo_text,
+                    update_reload_bar,
+                )
+                    .in_set(OnUpdate(AppState::InGame)),
+            );
+    }
+}
@@ -2,14 +2,25 @@ use bevy::prelude::*;
 
 use crate::AppState;
 
-mod arms;
+mod ammo_pickup;
+mod animation;
+pub mod arms;
+pub mod difficulty;
+mod enemy;
+mod explosion;
+mod game_over;
+mod health;
+mod hud;
 mod pause_menu;
-mod platform;
+pub mod platform;
 mod player;
-mod rocket_launcher;
+mod projectile;
+mod score;
+pub mod seed;
+mod weapon;
 
 #[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, States)]
-enum PausedState {
+pub enum PausedState {
     Paused,
     #[default]
     Playing,
@@ -22,9 +33,20 @@ impl Plugin for GamePlugin {
         app.add_state::<PausedState>()
             .add_plugin(platform::PlatformPlugin)
             .add_plugin(player::PlayerPlugin)
-            .add_plugin(rocket_launcher::RocketLauncherPlugin)
+            .add_plugin(weapon::WeaponPlugin)
+            .add_plugin(projectile::ProjectilePlugin)
+            .add_plugin(ammo_pickup::AmmoPickupPlugin)
+            .add_plugin(explosion::ExplosionPlugin)
+            .add_plugin(animation::AnimationPlugin)
             .add_plugin(arms::ArmsPlugin)
+            .add_plugin(enemy::EnemyPlugin)
+            .add_plugin(health::HealthPlugin)
             .add_plugin(pause_menu::PauseMenuPlugin)
+            .add_plugin(score::ScorePlugin)
+            .add_plugin(seed::SeedPlugin)
+            .add_plugin(difficulty::DifficultyPlugin)
+            .add_plugin(hud::HudPlugin)
+            .add_plugin(game_over::GameOverPlugin)
             .add_system(despawn_game.in_schedule(OnExit(AppState::InGame)));
     }
 }
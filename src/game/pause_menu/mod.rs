@@ -4,11 +4,12 @@ use bevy::prelude::*;
 use bevy_rapier2d::prelude::RapierConfiguration;
 
 use crate::{
+    actions::{Action, ActionInput},
     utils::interact_button::{self, *},
     AppState, SettingsState,
 };
 
-use super::PausedState;
+use super::{difficulty::Difficulty, seed::RunSeed, PausedState};
 
 use button::*;
 
@@ -48,6 +49,8 @@ fn create_pause_menu(
     mut commands: Commands,
     pause_menu: Query<Entity, With<PauseMenu>>,
     asset_server: Res<AssetServer>,
+    run_seed: Res<RunSeed>,
+    difficulty: Res<Difficulty>,
 ) {
     if pause_menu.iter().len() > 0 {
         return;
@@ -110,6 +113,15 @@ fn create_pause_menu(
                         }),
                     );
 
+                    parent.spawn(TextBundle::from_section(
+                        format!("Seed: {} ({})", run_seed.0, difficulty.label()),
+                        TextStyle {
+                            font: asset_server.load("century-gothic/gothic.ttf"),
+                            font_size: 25.0,
+                            color: Color::WHITE,
+                        },
+                    ));
+
                     parent
                         .spawn(NodeBundle {
                             style: Style {
@@ -159,9 +171,9 @@ fn despawn_pause_menu(mut commands: Commands, pause_menu: Query<Entity, With<Pau
 fn toggle_pause(
     mut commands: Commands,
     paused: Res<State<PausedState>>,
-    keys: Res<Input<KeyCode>>,
+    actions: ActionInput,
 ) {
-    if keys.just_pressed(KeyCode::Escape) {
+    if actions.just_pressed(Action::Pause) {
         if paused.0 == PausedState::Paused {
             commands.insert_resource(NextState(Some(PausedState::Playing)));
             commands.insert_resource(NextState(Some(SettingsState::Closed)));
@@ -172,11 +184,7 @@ fn toggle_pause(
 }
 
 fn pause_sim(paused: Res<State<PausedState>>, mut simulation_state: ResMut<RapierConfiguration>) {
-    if paused.0 == PausedState::Paused {
-        simulation_state.physics_pipeline_active = false;
-    } else {
-        simulation_state.physics_pipeline_active = true;
-    }
+    simulation_state.physics_pipeline_active = paused.0 != PausedState::Paused;
 }
 
 fn hide_on_settings_open(mut pause_menu: Query<&mut Visibility, With<PauseMenu>>) {
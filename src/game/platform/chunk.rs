@@ -0,0 +1,240 @@
+use std::{fs, path::PathBuf};
+
+use bevy::{asset::LoadState, ecs::system::SystemParam, prelude::*, reflect::TypeUuid};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    game::{ammo_pickup::spawn_ammo_pickup, GameEntity},
+    persistence::ConfigDir,
+    utils::ron_asset::RonAssetPlugin,
+};
+
+use super::{spawn_platform, Handcrafted, PlatformKind};
+
+//chance the next step of the climb is a whole chunk instead of a single platform
+const CHUNK_CHANCE: f64 = 0.2;
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ChunkPlatform {
+    pub position: Vec2,
+    #[serde(default)]
+    pub kind: PlatformKind,
+    #[serde(default)]
+    pub pickup: bool,
+}
+
+//a hand made piece of level, see assets/chunks. positions are from the chunk's
+//origin, which goes where the generator would have put the next platform,
+//so there should be a platform at (0, 0) and each one reachable from the last
+#[derive(Serialize, Deserialize, TypeUuid, Clone, Debug, Default)]
+#[uuid = "8f0b7c1e-5a44-4d3a-9b0e-2f6a1c3d9e71"]
+pub struct LevelChunk {
+    //doesn't show up below this many pixels
+    #[serde(default)]
+    pub min_height: f32,
+    pub platforms: Vec<ChunkPlatform>,
+    #[serde(default)]
+    pub enemy_spawns: Vec<Vec2>,
+}
+
+//a whole level made in the editor, played instead of generating one
+#[derive(Resource, Default)]
+pub struct CustomLayout(pub Option<LevelChunk>);
+
+impl CustomLayout {
+    //where the editor saves it, next to the settings since it's the player's own
+    pub fn path(config_dir: &ConfigDir) -> PathBuf {
+        config_dir.0.join("custom.layout.ron")
+    }
+
+    pub fn read(config_dir: &ConfigDir) -> Result<LevelChunk, String> {
+        let contents = fs::read_to_string(Self::path(config_dir)).map_err(|err| err.to_string())?;
+        ron::from_str(&contents).map_err(|err| err.to_string())
+    }
+}
+
+impl LevelChunk {
+    //mirrored flips it left to right around the origin
+    pub fn spawn(
+        &self,
+        commands: &mut Commands,
+        asset_server: &AssetServer,
+        origin: Vec3,
+        mirrored: bool,
+    ) {
+        let place = |position: Vec2| {
+            let position = if mirrored {
+                Vec2::new(-position.x, position.y)
+            } else {
+                position
+            };
+
+            origin + position.extend(0.0)
+        };
+
+        for chunk_platform in self.platforms.iter() {
+            let platform = spawn_platform(
+                commands,
+                asset_server,
+                Transform::from_translation(place(chunk_platform.position)),
+                chunk_platform.kind,
+            );
+
+            commands.entity(platform).insert(Handcrafted);
+
+            if chunk_platform.pickup {
+                spawn_ammo_pickup(commands, asset_server, platform);
+            }
+        }
+
+        for enemy_spawn in self.enemy_spawns.iter() {
+            commands.spawn((
+                SpatialBundle::from_transform(Transform::from_translation(place(*enemy_spawn))),
+                EnemySpawnPoint,
+                Name::new("Enemy spawn point"),
+                GameEntity,
+            ));
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct EnemySpawnPoint;
+
+//assets/chunks/manifest.chunks.ron, the paths of every chunk the generator can use
+#[derive(Deserialize, TypeUuid, Debug)]
+#[uuid = "3b9e6d21-7c4f-4a85-b0d3-9e1f5a2c8d47"]
+struct ChunkManifest(Vec<String>);
+
+#[derive(Resource)]
+struct ChunkManifestHandle(Handle<ChunkManifest>);
+
+//sorted by path whatever order the manifest lists them in, none until the manifest is in
+//or has failed to load
+#[derive(Resource, Default)]
+struct ChunkHandles(Option<Vec<Handle<LevelChunk>>>);
+
+fn load_chunk_manifest(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(ChunkManifestHandle(
+        asset_server.load("chunks/manifest.chunks.ron"),
+    ));
+}
+
+fn load_chunks(
+    mut ev_manifests: EventReader<AssetEvent<ChunkManifest>>,
+    manifest_handle: Res<ChunkManifestHandle>,
+    manifests: Res<Assets<ChunkManifest>>,
+    mut handles: ResMut<ChunkHandles>,
+    asset_server: Res<AssetServer>,
+) {
+    for ev_manifest in ev_manifests.iter() {
+        let (AssetEvent::Created { handle } | AssetEvent::Modified { handle }) = ev_manifest else {
+            continue;
+        };
+
+        if *handle != manifest_handle.0 {
+            continue;
+        }
+
+        let Some(manifest) = manifests.get(handle) else {
+            continue;
+        };
+
+        let mut paths = manifest.0.clone();
+        paths.sort();
+        paths.dedup();
+
+        handles.0 = Some(
+            paths
+                .iter()
+                .map(|path| asset_server.load(path.as_str()))
+                .collect(),
+        );
+    }
+
+    //generation waits on these, so anything that won't load is given up on instead of
+    //stopping the climb. the same files fail every run, so seeds still build the same
+    if handles.0.is_none() && asset_server.get_load_state(&manifest_handle.0) == LoadState::Failed {
+        warn!("couldn't load the chunk manifest, only generating single platforms");
+        handles.0 = Some(Vec::new());
+    }
+
+    let failed =
+        |handle: &Handle<LevelChunk>| asset_server.get_load_state(handle) == LoadState::Failed;
+
+    //only touched when something failed, so it isn't marked changed every frame
+    let Some(chunk_handles) = handles.0.as_ref() else {
+        return;
+    };
+
+    if !chunk_handles.iter().any(failed) {
+        return;
+    }
+
+    for handle in chunk_handles.iter().filter(|handle| failed(handle)) {
+        warn!(
+            "couldn't load chunk {:?}, leaving it out",
+            asset_server.get_handle_path(handle)
+        );
+    }
+
+    if let Some(chunk_handles) = handles.0.as_mut() {
+        chunk_handles.retain(|handle| !failed(handle));
+    }
+}
+
+#[derive(SystemParam)]
+pub(super) struct Chunks<'w> {
+    handles: Res<'w, ChunkHandles>,
+    chunks: Res<'w, Assets<LevelChunk>>,
+}
+
+impl<'w> Chunks<'w> {
+    //none until every chunk is in, picking from whichever loaded first would make
+    //what a seed builds depend on how fast the files come off the disk.
+    //ones that failed to load get dropped by load_chunks
+    pub(super) fn loaded(&self) -> Option<Vec<&LevelChunk>> {
+        self.handles
+            .0
+            .as_ref()?
+            .iter()
+            .map(|handle| self.chunks.get(handle))
+            .collect()
+    }
+}
+
+//rolls for whether to use a chunk at all, then picks one allowed at this height
+pub(super) fn pick<'a>(
+    chunks: &[&'a LevelChunk],
+    height: f32,
+    rand_gen: &mut impl Rng,
+) -> Option<&'a LevelChunk> {
+    if !rand_gen.gen_bool(CHUNK_CHANCE) {
+        return None;
+    }
+
+    let allowed = chunks
+        .iter()
+        .filter(|chunk| chunk.min_height <= height && !chunk.platforms.is_empty())
+        .collect::<Vec<_>>();
+
+    if allowed.is_empty() {
+        return None;
+    }
+
+    Some(allowed[rand_gen.gen_range(0..allowed.len())])
+}
+
+pub(super) struct ChunkPlugin;
+
+impl Plugin for ChunkPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugin(RonAssetPlugin::<LevelChunk>::new(&["chunk.ron"]))
+            .add_plugin(RonAssetPlugin::<ChunkManifest>::new(&["chunks.ron"]))
+            .init_resource::<CustomLayout>()
+            .init_resource::<ChunkHandles>()
+            .add_startup_system(load_chunk_manifest)
+            .add_system(load_chunks);
+    }
+}
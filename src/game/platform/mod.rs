@@ -0,0 +1,703 @@
+pub mod chunk;
+
+use crate::{
+    game::{
+        arms::ArmsTarget,
+        player::{JumpEnvelope, PlayerLandedOnEvent},
+        seed::RunRng,
+    },
+    AppState,
+};
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use rand::{distributions::WeightedIndex, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use self::chunk::{Chunks, CustomLayout, LevelChunk};
+
+use super::{GameEntity, PausedState};
+
+pub const PLATFORM_HALF_SIZE: Vec2 = Vec2::new(154.0, 38.0);
+
+//kinds get mixed in over this many pixels of height, after that the weights stay put
+const KIND_RAMP_HEIGHT: f32 = 10_000.0;
+
+//the player's feet sit this far below its center
+const PLAYER_FOOT_OFFSET: f32 = 50.0;
+
+#[derive(Component, Reflect)]
+pub struct Platform;
+
+//placed by a level chunk rather than the generator
+#[derive(Component)]
+pub struct Handcrafted;
+
+//how the generator spaces platforms, as fractions of what a jump can safely cover
+//(see JumpEnvelope::safe_height). the upper ends get clamped to 1 so every platform stays reachable
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlatformLayout {
+    //sideways distance between platform centers, of the furthest the player can get
+    pub gap: (f32, f32),
+    //height between platforms, of the top of the jump
+    pub rise: (f32, f32),
+}
+
+impl Default for PlatformLayout {
+    fn default() -> Self {
+        PlatformLayout {
+            gap: (0.6, 0.8),
+            rise: (0.8, 0.95),
+        }
+    }
+}
+
+//the layout at the bottom and once full_height is reached, linearly in between.
+//it only goes by height, never time, so a seed always builds the same level
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub struct PlatformLayoutCurve {
+    pub bottom: PlatformLayout,
+    pub top: PlatformLayout,
+    pub full_height: f32,
+}
+
+impl Default for PlatformLayoutCurve {
+    fn default() -> Self {
+        PlatformLayoutCurve {
+            bottom: PlatformLayout::default(),
+            top: PlatformLayout::default(),
+            full_height: 1.0,
+        }
+    }
+}
+
+impl PlatformLayoutCurve {
+    pub fn at(&self, height: f32) -> PlatformLayout {
+        let progress = (height / self.full_height).clamp(0.0, 1.0);
+        let between = |bottom: (f32, f32), top: (f32, f32)| {
+            (
+                bottom.0 + (top.0 - bottom.0) * progress,
+                bottom.1 + (top.1 - bottom.1) * progress,
+            )
+        };
+
+        PlatformLayout {
+            gap: between(self.bottom.gap, self.top.gap),
+            rise: between(self.bottom.rise, self.top.rise),
+        }
+    }
+}
+
+impl PlatformLayout {
+    fn next_offset(&self, envelope: &JumpEnvelope, rand_gen: &mut impl Rng) -> Vec2 {
+        let between = |(low, high): (f32, f32), rand_gen: &mut dyn RngCore| {
+            let fraction = low + (high - low) * rand_gen.gen::<f32>();
+            fraction.clamp(0.0, 1.0)
+        };
+
+        let rise = between(self.rise, rand_gen) * envelope.safe_height();
+
+        //the player can take off from the near edge and land on the far one
+        let furthest = envelope.safe_reach(rise).unwrap_or(0.0) + PLATFORM_HALF_SIZE.x * 2.0;
+
+        //never so close that the two overlap
+        let gap = (between(self.gap, rand_gen) * furthest).max(PLATFORM_HALF_SIZE.x * 2.0);
+
+        let direction = if rand_gen.gen_bool(0.5) { -1.0 } else { 1.0 };
+
+        Vec2::new(gap * direction, rise)
+    }
+}
+
+#[derive(Component, Reflect, Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub enum PlatformKind {
+    #[default]
+    Static,
+    MovingHorizontal,
+    MovingVertical,
+    Crumbling,
+    Bouncy,
+    OneWay,
+}
+
+impl PlatformKind {
+    pub const ALL: [PlatformKind; 6] = [
+        PlatformKind::Static,
+        PlatformKind::MovingHorizontal,
+        PlatformKind::MovingVertical,
+        PlatformKind::Crumbling,
+        PlatformKind::Bouncy,
+        PlatformKind::OneWay,
+    ];
+
+    //weight at the bottom and once the ramp is done, linearly in between
+    fn weights(&self) -> (f32, f32) {
+        match self {
+            PlatformKind::Static => (10.0, 3.0),
+            PlatformKind::MovingHorizontal => (1.0, 3.0),
+            PlatformKind::MovingVertical => (0.5, 2.0),
+            PlatformKind::Crumbling => (0.0, 3.0),
+            PlatformKind::Bouncy => (1.0, 1.5),
+            PlatformKind::OneWay => (1.0, 2.0),
+        }
+    }
+
+    fn weight_at(&self, height: f32) -> f32 {
+        let (bottom, top) = self.weights();
+        let progress = (height / KIND_RAMP_HEIGHT).clamp(0.0, 1.0);
+        bottom + (top - bottom) * progress
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PlatformKind::Static => "Static",
+            PlatformKind::MovingHorizontal => "Moving sideways",
+            PlatformKind::MovingVertical => "Moving up and down",
+            PlatformKind::Crumbling => "Crumbling",
+            PlatformKind::Bouncy => "Bouncy",
+            PlatformKind::OneWay => "One-way",
+        }
+    }
+
+    pub fn tint(&self) -> Color {
+        match self {
+            PlatformKind::Static => Color::WHITE,
+            PlatformKind::MovingHorizontal | PlatformKind::MovingVertical => {
+                Color::rgb(0.7, 0.85, 1.0)
+            }
+            PlatformKind::Crumbling => Color::rgb(0.8, 0.6, 0.45),
+            PlatformKind::Bouncy => Color::rgb(1.0, 0.6, 0.8),
+            PlatformKind::OneWay => Color::rgba(1.0, 1.0, 1.0, 0.6),
+        }
+    }
+
+    fn choose(height: f32, rand_gen: &mut impl Rng) -> PlatformKind {
+        let weights = PlatformKind::ALL.map(|kind| kind.weight_at(height));
+
+        match WeightedIndex::new(weights) {
+            Ok(index) => PlatformKind::ALL[index.sample(rand_gen)],
+            Err(_) => PlatformKind::Static,
+        }
+    }
+}
+
+//swings back and forth around where it was spawned
+#[derive(Component)]
+struct MovingPlatform {
+    origin: Vec3,
+    offset: Vec2,
+    period_seconds: f32,
+    elapsed_seconds: f32,
+}
+
+//breaks a moment after being landed on
+#[derive(Component)]
+struct CrumblingPlatform {
+    timer: Option<Timer>,
+}
+
+//launches whatever lands on it, in multiples of the player's normal jump
+#[derive(Component)]
+pub struct BouncyPlatform {
+    pub boost: f32,
+}
+
+//only solid once the player is above it, so it can be jumped up through
+#[derive(Component)]
+struct OneWayPlatform;
+
+fn create_starting_platform(
+    asset_server: Res<AssetServer>,
+    mut commands: Commands,
+    custom_layout: Res<CustomLayout>,
+) {
+    //the layout's origin takes the starting platform's place
+    if let Some(layout) = &custom_layout.0 {
+        layout.spawn(
+            &mut commands,
+            &asset_server,
+            Vec3::new(0.0, -150.0, 1.0),
+            false,
+        );
+        return;
+    }
+
+    spawn_platform(
+        &mut commands,
+        &asset_server,
+        Transform::from_xyz(0.0, -150.0, 1.0),
+        PlatformKind::Static,
+    );
+}
+
+fn create_platforms(
+    asset_server: Res<AssetServer>,
+    mut commands: Commands,
+    platforms: Query<(&Transform, Option<&MovingPlatform>), With<Platform>>,
+    mut run_rng: ResMut<RunRng>,
+    layout_curve: Res<PlatformLayoutCurve>,
+    rapier_config: Res<RapierConfiguration>,
+    chunks: Chunks,
+) {
+    let platform_count = platforms.iter().len();
+    let rand_gen = &mut run_rng.platforms;
+
+    //the level waits for the chunks, so it comes out the same however long they take to load
+    let Some(chunks) = chunks.loaded() else {
+        return;
+    };
+
+    if platform_count <= 7 {
+        let mut highest_platform_y = f32::NEG_INFINITY;
+        let mut highest_platform: Option<Transform> = None;
+
+        //moving platforms are measured from where they swing around,
+        //the player can wait for the part of the swing that makes the jump
+        for (platform, moving) in platforms.iter() {
+            let translation = moving.map_or(platform.translation, |moving| moving.origin);
+
+            if translation.y > highest_platform_y {
+                highest_platform_y = translation.y;
+                highest_platform = Some(Transform::from_translation(translation));
+            }
+        }
+
+        let Some(last_platform) = highest_platform else {
+            return;
+        };
+
+        let envelope = JumpEnvelope::new(rapier_config.gravity);
+
+        let layout = layout_curve.at(last_platform.translation.y);
+
+        let (origin, step) = next_step(
+            last_platform.translation,
+            &layout,
+            &envelope,
+            &chunks,
+            rand_gen,
+        );
+
+        match step {
+            NextStep::Chunk { chunk, mirrored } => {
+                chunk.spawn(&mut commands, &asset_server, origin, mirrored)
+            }
+            NextStep::Platform(kind) => {
+                spawn_platform(
+                    &mut commands,
+                    &asset_server,
+                    Transform::from_translation(origin),
+                    kind,
+                );
+            }
+        }
+    }
+}
+
+//what the generator puts above the highest platform
+enum NextStep<'a> {
+    //mirrored chunks carry on in the direction the climb was already heading
+    Chunk {
+        chunk: &'a LevelChunk,
+        mirrored: bool,
+    },
+    Platform(PlatformKind),
+}
+
+//everything random about the next step comes from `rand_gen`, so a seed always builds the same level
+fn next_step<'a>(
+    last_platform: Vec3,
+    layout: &PlatformLayout,
+    envelope: &JumpEnvelope,
+    chunks: &[&'a LevelChunk],
+    rand_gen: &mut impl Rng,
+) -> (Vec3, NextStep<'a>) {
+    let offset = layout.next_offset(envelope, rand_gen);
+    let origin = last_platform + offset.extend(0.0);
+
+    if let Some(chunk) = chunk::pick(chunks, origin.y, rand_gen) {
+        return (
+            origin,
+            NextStep::Chunk {
+                chunk,
+                mirrored: offset.x < 0.0,
+            },
+        );
+    }
+
+    let kind = PlatformKind::choose(last_platform.y, rand_gen);
+
+    (origin, NextStep::Platform(kind))
+}
+
+fn generating_platforms(custom_layout: Res<CustomLayout>) -> bool {
+    custom_layout.0.is_none()
+}
+
+fn spawn_platform(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    transform: Transform,
+    kind: PlatformKind,
+) -> Entity {
+    let mut platform = commands.spawn((
+        SpriteBundle {
+            texture: asset_server.load("platform.png"),
+            sprite: Sprite {
+                color: kind.tint(),
+                ..default()
+            },
+            transform,
+            ..default()
+        },
+        Platform,
+        kind,
+        RigidBody::Fixed,
+        Collider::cuboid(PLATFORM_HALF_SIZE.x, PLATFORM_HALF_SIZE.y),
+        Name::new("Platform"),
+        GameEntity,
+    ));
+
+    match kind {
+        PlatformKind::Static => {}
+        PlatformKind::MovingHorizontal | PlatformKind::MovingVertical => {
+            let offset = if kind == PlatformKind::MovingHorizontal {
+                Vec2::new(200.0, 0.0)
+            } else {
+                Vec2::new(0.0, 60.0)
+            };
+
+            platform.insert((
+                RigidBody::KinematicPositionBased,
+                MovingPlatform {
+                    origin: transform.translation,
+                    offset,
+                    period_seconds: 4.0,
+                    elapsed_seconds: 0.0,
+                },
+            ));
+        }
+        PlatformKind::Crumbling => {
+            platform.insert(CrumblingPlatform { timer: None });
+        }
+        PlatformKind::Bouncy => {
+            platform.insert(BouncyPlatform { boost: 2.0 });
+        }
+        PlatformKind::OneWay => {
+            platform.insert(OneWayPlatform);
+        }
+    }
+
+    platform.id()
+}
+
+fn move_platforms(mut platforms: Query<(&mut Transform, &mut MovingPlatform)>, time: Res<Time>) {
+    for (mut transform, mut moving) in platforms.iter_mut() {
+        moving.elapsed_seconds += time.delta_seconds();
+
+        let phase = moving.elapsed_seconds / moving.period_seconds * std::f32::consts::TAU;
+        transform.translation = moving.origin + (moving.offset * phase.sin()).extend(0.0);
+    }
+}
+
+fn crumble_platforms(
+    mut ev_player_land: EventReader<PlayerLandedOnEvent>,
+    mut platforms: Query<(Entity, &mut CrumblingPlatform, &mut Sprite)>,
+    mut commands: Commands,
+    time: Res<Time>,
+) {
+    for land_event in ev_player_land.iter() {
+        if let Ok((_, mut crumbling, _)) = platforms.get_mut(land_event.0) {
+            if crumbling.timer.is_none() {
+                crumbling.timer = Some(Timer::from_seconds(0.6, TimerMode::Once));
+            }
+        }
+    }
+
+    for (platform, mut crumbling, mut sprite) in platforms.iter_mut() {
+        let Some(timer) = crumbling.timer.as_mut() else {
+            continue;
+        };
+
+        timer.tick(time.delta());
+        sprite.color.set_a(1.0 - timer.percent() * 0.7);
+
+        if timer.finished() {
+            commands.entity(platform).despawn_recursive();
+        }
+    }
+}
+
+fn update_one_way_platforms(
+    target_query: Query<&Transform, (With<ArmsTarget>, Without<OneWayPlatform>)>,
+    platforms: Query<(Entity, &Transform, Option<&ColliderDisabled>), With<OneWayPlatform>>,
+    mut commands: Commands,
+) {
+    let Ok(target) = target_query.get_single() else {
+        return;
+    };
+
+    let feet = target.translation.y - PLAYER_FOOT_OFFSET;
+
+    for (platform, transform, disabled) in platforms.iter() {
+        //a bit of leeway so landing right on the edge still counts
+        let solid = feet >= transform.translation.y + PLATFORM_HALF_SIZE.y - 10.0;
+
+        if solid && disabled.is_some() {
+            commands.entity(platform).remove::<ColliderDisabled>();
+        } else if !solid && disabled.is_none() {
+            commands.entity(platform).insert(ColliderDisabled);
+        }
+    }
+}
+
+fn delete_platform(
+    platforms: Query<(Entity, &Transform), With<Platform>>,
+    mut ev_player_land: EventReader<PlayerLandedOnEvent>,
+    mut commands: Commands,
+) {
+    if platforms.iter().len() == 1 {
+        return;
+    }
+
+    for land_event in ev_player_land.iter() {
+        let mut lowest_platform: Option<(Entity, Transform)> = None;
+        let mut lowest_platform_y = f32::INFINITY;
+
+        for (platform, transform) in platforms.iter() {
+            if transform.translation.y < lowest_platform_y {
+                lowest_platform_y = transform.translation.y;
+                lowest_platform = Some((platform, *transform));
+            }
+        }
+
+        if let Some((lowest_platform, _)) = lowest_platform {
+            if lowest_platform != land_event.0 {
+                commands.entity(lowest_platform).despawn_recursive();
+            }
+        } else {
+            panic!("lowest platform not found")
+        }
+    }
+}
+
+pub struct PlatformPlugin;
+
+impl Plugin for PlatformPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugin(chunk::ChunkPlugin)
+            .register_type::<Platform>()
+            .init_resource::<PlatformLayoutCurve>()
+            .register_type::<PlatformKind>()
+            .add_system(create_starting_platform.in_schedule(OnEnter(AppState::InGame)))
+            .add_systems(
+                (
+                    create_platforms.run_if(generating_platforms),
+                    delete_platform,
+                    move_platforms,
+                    crumble_platforms,
+                    update_one_way_platforms,
+                )
+                    .in_set(OnUpdate(AppState::InGame))
+                    .in_set(OnUpdate(PausedState::Playing)),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{chunk::ChunkPlatform, *};
+
+    use crate::game::seed::RunRng;
+
+    fn test_chunks() -> Vec<LevelChunk> {
+        vec![
+            LevelChunk {
+                min_height: 0.0,
+                platforms: vec![
+                    ChunkPlatform {
+                        position: Vec2::ZERO,
+                        kind: PlatformKind::Static,
+                        pickup: false,
+                    },
+                    ChunkPlatform {
+                        position: Vec2::new(320.0, 120.0),
+                        kind: PlatformKind::Bouncy,
+                        pickup: true,
+                    },
+                ],
+                enemy_spawns: Vec::new(),
+            },
+            LevelChunk {
+                min_height: 2000.0,
+                platforms: vec![
+                    ChunkPlatform {
+                        position: Vec2::ZERO,
+                        kind: PlatformKind::Crumbling,
+                        pickup: false,
+                    },
+                    ChunkPlatform {
+                        position: Vec2::new(-330.0, 110.0),
+                        kind: PlatformKind::OneWay,
+                        pickup: false,
+                    },
+                ],
+                enemy_spawns: vec![Vec2::new(0.0, 200.0)],
+            },
+        ]
+    }
+
+    //does what create_platforms does over a run, minus the entities
+    fn generate(seed: u64, steps: usize) -> Vec<(Vec3, PlatformKind)> {
+        let chunks = test_chunks();
+        let chunks = chunks.iter().collect::<Vec<_>>();
+        let envelope = JumpEnvelope::new(RapierConfiguration::default().gravity);
+        let layout_curve = PlatformLayoutCurve {
+            bottom: PlatformLayout::default(),
+            top: PlatformLayout {
+                gap: (0.8, 1.0),
+                rise: (0.9, 1.0),
+            },
+            full_height: 5000.0,
+        };
+        let mut run_rng = RunRng::new(seed);
+
+        let mut platforms = vec![(Vec3::new(0.0, -150.0, 1.0), PlatformKind::Static)];
+
+        for _ in 0..steps {
+            let highest = platforms
+                .iter()
+                .map(|(translation, _)| *translation)
+                .max_by(|a, b| a.y.total_cmp(&b.y))
+                .unwrap();
+
+            let layout = layout_curve.at(highest.y);
+            let (origin, step) =
+                next_step(highest, &layout, &envelope, &chunks, &mut run_rng.platforms);
+
+            match step {
+                NextStep::Chunk { chunk, mirrored } => {
+                    let facing = Vec2::new(if mirrored { -1.0 } else { 1.0 }, 1.0);
+
+                    platforms.extend(chunk.platforms.iter().map(|platform| {
+                        (
+                            origin + (platform.position * facing).extend(0.0),
+                            platform.kind,
+                        )
+                    }))
+                }
+                NextStep::Platform(kind) => platforms.push((origin, kind)),
+            }
+        }
+
+        platforms
+    }
+
+    #[test]
+    fn same_seed_same_platforms() {
+        for seed in [0, 1, 42, 123_456_789, 999_999_999] {
+            let first = generate(seed, 300);
+            let second = generate(seed, 300);
+
+            assert_eq!(first, second, "seed {seed} built two different levels");
+        }
+    }
+
+    #[test]
+    fn layout_ramps_with_height() {
+        let layout_curve = PlatformLayoutCurve {
+            bottom: PlatformLayout {
+                gap: (0.2, 0.4),
+                rise: (0.5, 0.6),
+            },
+            top: PlatformLayout {
+                gap: (0.6, 0.8),
+                rise: (0.7, 1.0),
+            },
+            full_height: 1000.0,
+        };
+
+        assert_eq!(layout_curve.at(-150.0), layout_curve.bottom);
+        assert_eq!(layout_curve.at(0.0), layout_curve.bottom);
+        assert_eq!(layout_curve.at(1000.0), layout_curve.top);
+        assert_eq!(layout_curve.at(25_000.0), layout_curve.top);
+
+        let halfway = layout_curve.at(500.0);
+        assert!((halfway.gap.0 - 0.4).abs() < 1e-6 && (halfway.gap.1 - 0.6).abs() < 1e-6);
+        assert!((halfway.rise.0 - 0.6).abs() < 1e-6 && (halfway.rise.1 - 0.8).abs() < 1e-6);
+    }
+
+    #[test]
+    fn chunks_go_both_ways() {
+        let chunks = test_chunks();
+        let chunks = chunks.iter().collect::<Vec<_>>();
+        let envelope = JumpEnvelope::new(RapierConfiguration::default().gravity);
+        let layout = PlatformLayout::default();
+        let mut run_rng = RunRng::new(7);
+
+        let mut directions = Vec::new();
+
+        for _ in 0..200 {
+            let (origin, step) = next_step(
+                Vec3::ZERO,
+                &layout,
+                &envelope,
+                &chunks,
+                &mut run_rng.platforms,
+            );
+
+            if let NextStep::Chunk { mirrored, .. } = step {
+                assert_eq!(mirrored, origin.x < 0.0);
+                directions.push(mirrored);
+            }
+        }
+
+        assert!(directions.contains(&true) && directions.contains(&false));
+    }
+
+    //knob values from the presets and past both ends, which get clamped
+    const KNOBS: [f32; 7] = [-0.5, 0.0, 0.5, 0.8, 0.95, 1.0, 1.5];
+
+    #[test]
+    fn every_offset_is_reachable() {
+        let envelope = JumpEnvelope::new(RapierConfiguration::default().gravity);
+
+        for gap in KNOBS.iter().flat_map(|low| KNOBS.map(|high| (*low, high))) {
+            for rise in KNOBS.iter().flat_map(|low| KNOBS.map(|high| (*low, high))) {
+                let layout = PlatformLayout { gap, rise };
+
+                for seed in 0..20 {
+                    let mut run_rng = RunRng::new(seed);
+
+                    for _ in 0..10 {
+                        let offset = layout.next_offset(&envelope, &mut run_rng.platforms);
+
+                        assert!(
+                            (0.0..=envelope.safe_height()).contains(&offset.y),
+                            "{layout:?} seed {seed} put a platform {} up",
+                            offset.y
+                        );
+
+                        //from the edge of one platform to the edge of the next
+                        let jump = offset.x.abs() - PLATFORM_HALF_SIZE.x * 2.0;
+                        let reach = envelope.safe_reach(offset.y).unwrap();
+
+                        assert!(
+                            jump <= reach + 0.01,
+                            "{layout:?} seed {seed} needs a {jump} jump at {} up, only {reach} reachable",
+                            offset.y
+                        );
+                        assert!(
+                            jump >= -0.01,
+                            "{layout:?} seed {seed} overlapped two platforms"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn different_seeds_different_platforms() {
+        assert_ne!(generate(1, 50), generate(2, 50));
+    }
+}
@@ -2,16 +2,106 @@ mod player_jump;
 mod player_move;
 
 use crate::{
-    camera::*, game::arms::ArmsTarget, game::rocket_launcher::RocketLauncherHolderSpawns, AppState,
+    camera::*,
+    game::animation::SpriteAnimation,
+    game::arms::{Arms, ArmsTarget},
+    game::health::{Died, Health},
+    game::weapon::WeaponHolderSpawns,
+    AppState,
 };
 use bevy::{prelude::*, sprite::Anchor};
 use bevy_rapier2d::prelude::*;
 
-use self::player_jump::{ground_player, jump_player};
+use self::player_jump::{animate_player_legs, ground_player, jump_player};
 use self::player_move::move_player;
 
 use super::{GameEntity, PausedState};
 
+const MAX_SPEED: f32 = 380.0;
+const JUMP_FORCE: f32 = 300.0;
+const GRAVITY_SCALE: f32 = 3.0;
+
+const MAX_HEALTH: u32 = 3;
+//after getting hurt, so one enemy can't take it all in a single pass
+const INVULNERABLE_SECONDS: f32 = 1.0;
+
+//added to the run speed every frame a move key is held, in px/s
+const RUN_ACCELERATION: f32 = 50.0;
+
+//physics never steps more than this at once (see setup_rapier), slower frame rates
+//slow the game down instead. fewer frames a second also means less run acceleration
+const SLOWEST_FPS: f32 = 30.0;
+
+//share of the jump platform generation counts on. the envelope is for a point at the feet,
+//the capsule's rounded bottom and rapier's contact skin catch on edges that point would clear
+const JUMP_MARGIN: f32 = 0.85;
+
+//where a jump from standing still at the edge can take the player, platform generation keeps inside it.
+//it's worked out for the slowest frame rate, anything faster jumps at least as far and as high
+#[derive(Clone, Copy, Debug)]
+pub struct JumpEnvelope {
+    jump_speed: f32,
+    run_speed: f32,
+    //in px/s^2, the player speeds up in the air as well
+    run_acceleration: f32,
+    //downwards, in px/s^2
+    gravity: f32,
+}
+
+impl JumpEnvelope {
+    pub fn new(world_gravity: Vec2) -> Self {
+        let gravity = -world_gravity.y * GRAVITY_SCALE;
+        let step = 1.0 / SLOWEST_FPS;
+
+        JumpEnvelope {
+            //rapier takes gravity off the velocity before moving by it, which over whole
+            //steps is the same arc as a smooth jump half a step's worth of gravity slower
+            jump_speed: JUMP_FORCE - gravity * step / 2.0,
+            run_speed: MAX_SPEED,
+            run_acceleration: RUN_ACCELERATION / step,
+            gravity,
+        }
+    }
+
+    pub fn apex_height(&self) -> f32 {
+        self.jump_speed * self.jump_speed / (2.0 * self.gravity)
+    }
+
+    //furthest sideways the player's feet can get while landing `rise` higher
+    //than they took off, none if that's above the apex
+    pub fn reach(&self, rise: f32) -> Option<f32> {
+        let discriminant = self.jump_speed * self.jump_speed - 2.0 * self.gravity * rise;
+
+        if self.gravity <= 0.0 || discriminant < 0.0 {
+            return None;
+        }
+
+        //the later of the two times the arc passes that height, on the way down
+        let time = (self.jump_speed + discriminant.sqrt()) / self.gravity;
+
+        //getting up to full speed, then running at it the rest of the way
+        let speeding_up = (self.run_speed / self.run_acceleration).min(time);
+
+        Some(
+            self.run_acceleration * speeding_up * speeding_up / 2.0
+                + self.run_speed * (time - speeding_up),
+        )
+    }
+
+    //what platforms get spaced by, comfortably inside the jump instead of right at its edge
+    pub fn safe_height(&self) -> f32 {
+        self.apex_height() * JUMP_MARGIN
+    }
+
+    pub fn safe_reach(&self, rise: f32) -> Option<f32> {
+        if rise > self.safe_height() {
+            return None;
+        }
+
+        self.reach(rise).map(|reach| reach * JUMP_MARGIN)
+    }
+}
+
 #[derive(Component, Reflect)]
 struct PlayerMove {
     speed: f32,
@@ -58,18 +148,18 @@ fn follow_eyes(
 fn spawn_player(
     asset_server: Res<AssetServer>,
     mut commands: Commands,
-    mut ev_rocket_launcher_holder_spawns: EventWriter<RocketLauncherHolderSpawns>,
+    mut ev_weapon_holder_spawns: EventWriter<WeaponHolderSpawns>,
 ) {
     let player = commands
         .spawn((
             PlayerMove {
-                speed: 50.0,
-                max_speed: 380.0,
+                speed: RUN_ACCELERATION,
+                max_speed: MAX_SPEED,
                 seconds_to_stop_after_key_release: 0.3,
             },
             PlayerJump {
                 grounded: true,
-                jump_force: 300.0,
+                jump_force: JUMP_FORCE,
             },
             SpriteBundle {
                 texture: asset_server.load("player/cupcake.png"),
@@ -83,11 +173,13 @@ fn spawn_player(
             RigidBody::Dynamic,
             Collider::capsule(Vec2::new(0.0, -195.0), Vec2::new(0.0, 0.0), 300.0),
             Velocity::default(),
+            ReadMassProperties::default(),
             LockedAxes::ROTATION_LOCKED_Z,
-            GravityScale(3.0),
+            GravityScale(GRAVITY_SCALE),
             ActiveEvents::COLLISION_EVENTS,
+            Health::new(MAX_HEALTH, INVULNERABLE_SECONDS),
             Name::new("Player"),
-            FollowedByCamera,
+            FollowedByCamera::default(),
             GameEntity,
             ArmsTarget,
         ))
@@ -111,9 +203,8 @@ fn spawn_player(
     commands.entity(player).with_children(|parent| {
         parent.spawn((
             PlayerLegs,
-            SpriteBundle {
-                texture: asset_server.load("player/legs.png"),
-                sprite: Sprite {
+            SpriteSheetBundle {
+                sprite: TextureAtlasSprite {
                     anchor: Anchor::TopCenter,
                     ..default()
                 },
@@ -124,6 +215,7 @@ fn spawn_player(
                 },
                 ..default()
             },
+            SpriteAnimation::new(asset_server.load("player/legs.anim.ron"), "idle"),
             Name::new("Legs"),
         ));
 
@@ -144,9 +236,30 @@ fn spawn_player(
         ));
     });
 
-    ev_rocket_launcher_holder_spawns.send(RocketLauncherHolderSpawns(player))
+    ev_weapon_holder_spawns.send(WeaponHolderSpawns(player))
+}
+
+fn kill_player(
+    mut ev_died: EventReader<Died>,
+    player_query: Query<(), With<PlayerMove>>,
+    arms_query: Query<(), With<Arms>>,
+    mut commands: Commands,
+    mut ev_player_died: EventWriter<PlayerDied>,
+) {
+    for died in ev_died.iter() {
+        //an arm grabs the player instead, and decides how that ends
+        if died.killer.is_some_and(|killer| arms_query.contains(killer)) {
+            continue;
+        }
+
+        if player_query.contains(died.entity) {
+            commands.entity(died.entity).despawn_recursive();
+            ev_player_died.send(PlayerDied);
+        }
+    }
 }
 
+#[allow(clippy::type_complexity)]
 fn player_death(
     mut commands: Commands,
     player_query: Query<(), (With<PlayerMove>, Without<PlayerEyes>, Without<PlayerLegs>)>,
@@ -169,6 +282,8 @@ pub struct PlayerPlugin;
 
 pub struct PlayerLandedOnEvent(pub Entity);
 
+pub struct PlayerDied;
+
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
@@ -177,6 +292,8 @@ impl Plugin for PlayerPlugin {
                 move_player,
                 jump_player,
                 ground_player,
+                animate_player_legs.after(jump_player).after(ground_player),
+                kill_player,
                 player_death,
             )
                 .in_set(OnUpdate(AppState::InGame))
@@ -185,6 +302,126 @@ impl Plugin for PlayerPlugin {
         .add_system(spawn_player.in_schedule(OnEnter(AppState::InGame)))
         .register_type::<PlayerMove>()
         .register_type::<PlayerJump>()
-        .add_event::<PlayerLandedOnEvent>();
+        .add_event::<PlayerLandedOnEvent>()
+        .add_event::<PlayerDied>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //a jump from standing still, a frame at a time the way move_player and rapier do it.
+    //gives the highest point and how far sideways it was going down through each height
+    fn jump(fps: f32, heights: &[f32]) -> (f32, Vec<Option<f32>>) {
+        let gravity = RapierConfiguration::default().gravity.y * GRAVITY_SCALE;
+        let step = 1.0 / fps;
+
+        let mut position = Vec2::ZERO;
+        let mut velocity = Vec2::new(0.0, JUMP_FORCE);
+        let mut highest: f32 = 0.0;
+        let mut reached = vec![None; heights.len()];
+
+        while position.y >= 0.0 {
+            velocity.x = (velocity.x + RUN_ACCELERATION).min(MAX_SPEED);
+            velocity.y += gravity * step;
+
+            let last = position;
+            position += velocity * step;
+            highest = highest.max(position.y);
+
+            for (height, reached) in heights.iter().zip(reached.iter_mut()) {
+                if velocity.y < 0.0 && last.y >= *height && position.y < *height {
+                    let along = (last.y - height) / (last.y - position.y);
+                    *reached = Some(last.x + (position.x - last.x) * along);
+                }
+            }
+        }
+
+        (highest, reached)
+    }
+
+    fn envelope() -> JumpEnvelope {
+        JumpEnvelope::new(RapierConfiguration::default().gravity)
+    }
+
+    #[test]
+    fn apex_is_reached_at_any_frame_rate() {
+        let envelope = envelope();
+
+        for fps in [SLOWEST_FPS, 60.0, 144.0, 240.0] {
+            let (highest, _) = jump(fps, &[]);
+            assert!(
+                highest >= envelope.apex_height() - 0.01,
+                "{fps} fps only got {highest} up, the envelope says {}",
+                envelope.apex_height()
+            );
+        }
+    }
+
+    #[test]
+    fn reach_is_covered_at_any_frame_rate() {
+        let envelope = envelope();
+        let heights = (0..=20)
+            .map(|i| envelope.apex_height() * i as f32 / 20.0)
+            .collect::<Vec<_>>();
+
+        for fps in [SLOWEST_FPS, 60.0, 144.0, 240.0] {
+            let (_, reached) = jump(fps, &heights);
+
+            for (height, reached) in heights.iter().zip(reached) {
+                let reach = envelope.reach(*height).unwrap();
+                let Some(reached) = reached else {
+                    //right at the apex it can go over without ever coming back down past it
+                    assert!(
+                        *height >= envelope.apex_height() - 1.0,
+                        "{fps} fps never came down past {height}"
+                    );
+                    continue;
+                };
+
+                assert!(
+                    reached >= reach - 0.01,
+                    "{fps} fps got {reached} sideways at {height} up, the envelope says {reach}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn starting_from_standing_still_costs_reach() {
+        let envelope = envelope();
+        let time = 2.0 * envelope.jump_speed / envelope.gravity;
+
+        let reach = envelope.reach(0.0).unwrap();
+        assert!(reach < MAX_SPEED * time);
+        assert!(reach > MAX_SPEED * time - MAX_SPEED * MAX_SPEED / envelope.run_acceleration);
+    }
+
+    #[test]
+    fn nothing_above_the_apex() {
+        let envelope = envelope();
+
+        assert!(envelope.reach(envelope.apex_height() + 1.0).is_none());
+        assert!(envelope.reach(envelope.apex_height() - 1.0).is_some());
+    }
+
+    #[test]
+    fn safe_jump_keeps_a_margin() {
+        let envelope = envelope();
+
+        assert!(envelope.safe_height() < envelope.apex_height());
+        assert!(envelope.safe_reach(envelope.safe_height() + 1.0).is_none());
+
+        for i in 0..=20 {
+            let rise = envelope.safe_height() * i as f32 / 20.0;
+            let safe_reach = envelope.safe_reach(rise).unwrap();
+            let reach = envelope.reach(rise).unwrap();
+
+            assert!(
+                safe_reach <= reach * JUMP_MARGIN + 0.01,
+                "{safe_reach} safe at {rise} up, the whole jump gets {reach}"
+            );
+        }
     }
 }
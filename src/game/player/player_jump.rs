@@ -1,21 +1,36 @@
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 
-use crate::game::platform::Platform;
+use crate::{
+    actions::{Action, ActionInput},
+    game::{
+        animation::{AnimationFinished, SpriteAnimation},
+        platform::{BouncyPlatform, Platform},
+    },
+    game_audio::{PlaySfx, SfxCue},
+};
 
 use super::{PlayerGroundSensor, PlayerJump, PlayerLandedOnEvent, PlayerLegs};
 
+//slower than this on the ground and the legs stand still
+const WALK_SPEED: f32 = 50.0;
+
 pub(super) fn ground_player(
     mut collision_events: EventReader<CollisionEvent>,
     sensor_query: Query<Entity, With<PlayerGroundSensor>>,
-    mut player_jump_query: Query<&mut PlayerJump, Without<PlayerGroundSensor>>,
+    mut player_jump_query: Query<
+        (Entity, &mut PlayerJump, &mut Velocity),
+        Without<PlayerGroundSensor>,
+    >,
     mut ev_landed: EventWriter<PlayerLandedOnEvent>,
     platforms: Query<Entity, With<Platform>>,
+    bouncy_platforms: Query<&BouncyPlatform>,
+    mut ev_sfx: EventWriter<PlaySfx>,
 ) {
     let Ok(sensor) = sensor_query.get_single() else {
       return;
   };
-    let Ok(mut player_jump) = player_jump_query.get_single_mut() else {
+    let Ok((player, mut player_jump, mut velocity)) = player_jump_query.get_single_mut() else {
       return;
   };
 
@@ -32,7 +47,18 @@ pub(super) fn ground_player(
                     }
                 }
 
+                if !player_jump.grounded {
+                    ev_sfx.send(PlaySfx::following(SfxCue::Land, player));
+                }
+
                 player_jump.grounded = true;
+
+                //straight back up without waiting for a jump
+                let other = if h1 == &sensor { h2 } else { h1 };
+                if let Ok(bouncy) = bouncy_platforms.get(*other) {
+                    velocity.linvel.y = player_jump.jump_force * bouncy.boost;
+                    player_jump.grounded = false;
+                }
             }
         }
 
@@ -45,30 +71,51 @@ pub(super) fn ground_player(
 }
 
 pub(super) fn jump_player(
-    mut player_query: Query<(&mut Velocity, &mut PlayerJump), Without<PlayerLegs>>,
-    mut player_legs_query: Query<&mut Transform, (With<PlayerLegs>, Without<PlayerJump>)>,
-    keys: Res<Input<KeyCode>>,
-    mut time_since_jump: Local<f32>,
-    time: Res<Time>,
+    mut player_query: Query<(Entity, &mut Velocity, &mut PlayerJump)>,
+    mut player_legs_query: Query<&mut SpriteAnimation, With<PlayerLegs>>,
+    actions: ActionInput,
+    mut ev_sfx: EventWriter<PlaySfx>,
 ) {
-    let Ok((mut velocity, mut player_jump)) = player_query.get_single_mut() else {
+    let Ok((player, mut velocity, mut player_jump)) = player_query.get_single_mut() else {
       return;
   };
-    let Ok(mut legs_transform) = player_legs_query.get_single_mut() else {
+    let Ok(mut legs_animation) = player_legs_query.get_single_mut() else {
       return;
   };
 
-    if keys.just_pressed(KeyCode::W) && player_jump.grounded {
+    if actions.just_pressed(Action::Jump) && player_jump.grounded {
         velocity.linvel.y = player_jump.jump_force;
         player_jump.grounded = false;
 
-        *time_since_jump = 0.0;
-    } else {
-        *time_since_jump += time.delta_seconds();
-        legs_transform.scale.y = 2.0;
+        legs_animation.play("jump");
+        ev_sfx.send(PlaySfx::following(SfxCue::Jump, player));
     }
+}
+
+//the jump plays out on its own, after that the legs just follow what the player is doing
+pub(super) fn animate_player_legs(
+    player_query: Query<(&Velocity, &PlayerJump)>,
+    mut player_legs_query: Query<(Entity, &mut SpriteAnimation), With<PlayerLegs>>,
+    mut ev_animation_finished: EventReader<AnimationFinished>,
+) {
+    let Ok((velocity, player_jump)) = player_query.get_single() else {
+      return;
+  };
+    let Ok((legs, mut legs_animation)) = player_legs_query.get_single_mut() else {
+      return;
+  };
+
+    let jump_finished = ev_animation_finished
+        .iter()
+        .any(|finished| finished.entity == legs && finished.clip == "jump");
 
-    if *time_since_jump < 0.2 {
-        legs_transform.scale.y = 1.8;
+    if !player_jump.grounded {
+        if jump_finished || legs_animation.clip() != "jump" {
+            legs_animation.play("fall");
+        }
+    } else if velocity.linvel.x.abs() > WALK_SPEED {
+        legs_animation.play("walk");
+    } else {
+        legs_animation.play("idle");
     }
 }
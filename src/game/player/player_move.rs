@@ -1,11 +1,13 @@
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 
+use crate::actions::{Action, ActionInput};
+
 use super::PlayerMove;
 
 pub(super) fn move_player(
     mut player_query: Query<(&mut Velocity, &PlayerMove)>,
-    keys: Res<Input<KeyCode>>,
+    actions: ActionInput,
     mut stopped_player: Local<bool>,
     mut x_velocity_when_stopped: Local<f32>,
     mut counter: Local<f32>,
@@ -17,20 +19,20 @@ pub(super) fn move_player(
       return;
   };
 
-    if keys.pressed(KeyCode::A) {
+    if actions.pressed(Action::MoveLeft) {
         velocity.linvel = Vec2::new(
             (velocity.linvel.x - player.speed).max(-player.max_speed),
             velocity.linvel.y,
         );
         *stopped_player = false;
-    } else if keys.pressed(KeyCode::D) {
+    } else if actions.pressed(Action::MoveRight) {
         velocity.linvel = Vec2::new(
             (velocity.linvel.x + player.speed).min(player.max_speed),
             velocity.linvel.y,
         );
         *stopped_player = false;
     } else {
-        if *stopped_player == true {
+        if *stopped_player {
             let mut stopped_amount = 1.0 - (*counter / player.seconds_to_stop_after_key_release);
 
             if stopped_amount > 0.0 {
@@ -0,0 +1,282 @@
+use bevy::{ecs::system::SystemParam, prelude::*, utils::HashSet};
+use bevy_rapier2d::prelude::*;
+
+use crate::{
+    camera::GameCamera,
+    game::{
+        ammo_pickup::AmmoPickup,
+        explosion::{Explosion, ExplosionEvent},
+    },
+    AppState,
+};
+
+use super::PausedState;
+
+//anything still around after this is gone without exploding, whatever its fuse says
+const MAX_LIFETIME_SECONDS: f32 = 10.0;
+
+//how far outside the camera view a projectile can get before it's dropped
+const CULL_MARGIN: f32 = 1000.0;
+
+//retired projectiles past this many get despawned instead of kept for reuse
+const MAX_POOLED: usize = 64;
+
+//goes off after touching enough things, or when the fuse runs out
+#[derive(Component)]
+pub struct Projectile {
+    pub fuse: Timer,
+    //0 leaves it to the fuse
+    pub contacts_left: u32,
+    pub explosion: Explosion,
+    pub lifetime: Timer,
+}
+
+impl Projectile {
+    pub fn new(fuse_seconds: f32, contacts_to_detonate: u32, explosion: Explosion) -> Self {
+        Projectile {
+            fuse: Timer::from_seconds(fuse_seconds, TimerMode::Once),
+            contacts_left: contacts_to_detonate,
+            explosion,
+            lifetime: Timer::from_seconds(MAX_LIFETIME_SECONDS, TimerMode::Once),
+        }
+    }
+}
+
+//hidden, disabled projectile entities waiting to be fired again
+#[derive(Resource, Default)]
+struct ProjectilePool(Vec<Entity>);
+
+#[derive(SystemParam)]
+pub struct Projectiles<'w, 's> {
+    commands: Commands<'w, 's>,
+    pool: ResMut<'w, ProjectilePool>,
+}
+
+impl<'w, 's> Projectiles<'w, 's> {
+    //the bundle has to set everything a projectile needs, a reused entity keeps whatever it had
+    pub fn spawn(&mut self, bundle: impl Bundle) {
+        if let Some(entity) = self.pool.0.pop() {
+            self.commands
+                .entity(entity)
+                .remove::<(RigidBodyDisabled, ColliderDisabled)>()
+                .insert((bundle, ExternalImpulse::default()));
+        } else {
+            self.commands.spawn(bundle);
+        }
+    }
+
+    pub fn retire(&mut self, entity: Entity) {
+        //can get retired twice in a frame, say by exploding just as it leaves the view
+        if self.pool.0.contains(&entity) {
+            return;
+        }
+
+        if self.pool.0.len() >= MAX_POOLED {
+            self.commands.entity(entity).despawn_recursive();
+            return;
+        }
+
+        self.commands.entity(entity).remove::<Projectile>().insert((
+            RigidBodyDisabled,
+            ColliderDisabled,
+            Visibility::Hidden,
+        ));
+        self.pool.0.push(entity);
+    }
+}
+
+fn detonate_projectiles(
+    mut ev_collision: EventReader<CollisionEvent>,
+    mut projectile_query: Query<(Entity, &GlobalTransform, &mut Projectile)>,
+    pickups: Query<(), With<AmmoPickup>>,
+    mut projectiles: Projectiles,
+    mut ev_explosion: EventWriter<ExplosionEvent>,
+    time: Res<Time>,
+) {
+    let mut touched = HashSet::new();
+
+    for collision_event in ev_collision.iter() {
+        if let CollisionEvent::Started(h1, h2, _event_flag) = collision_event {
+            //flying through a pickup shouldn't set anything off
+            if pickups.contains(*h1) || pickups.contains(*h2) {
+                continue;
+            }
+
+            touched.insert(*h1);
+            touched.insert(*h2);
+        }
+    }
+
+    for (projectile_entity, transform, mut projectile) in projectile_query.iter_mut() {
+        projectile.fuse.tick(time.delta());
+
+        if touched.contains(&projectile_entity) && projectile.contacts_left > 0 {
+            projectile.contacts_left -= 1;
+
+            if projectile.contacts_left == 0 {
+                //make sure the fuse check below sets it off
+                let duration = projectile.fuse.duration();
+                projectile.fuse.set_elapsed(duration);
+            }
+        }
+
+        if projectile.fuse.finished() {
+            projectiles.retire(projectile_entity);
+
+            ev_explosion.send(ExplosionEvent {
+                position: transform.translation().truncate(),
+                explosion: projectile.explosion,
+            });
+        }
+    }
+}
+
+fn cull_projectiles(
+    mut projectile_query: Query<(Entity, &GlobalTransform, &mut Projectile)>,
+    camera_query: Query<(&GlobalTransform, &OrthographicProjection), With<GameCamera>>,
+    mut projectiles: Projectiles,
+    time: Res<Time>,
+) {
+    let Ok((camera_transform, projection)) = camera_query.get_single() else {
+        return;
+    };
+
+    let camera_position = camera_transform.translation().truncate();
+    let bounds = Rect::from_corners(
+        projection.area.min + camera_position,
+        projection.area.max + camera_position,
+    )
+    .inset(CULL_MARGIN);
+
+    for (projectile_entity, transform, mut projectile) in projectile_query.iter_mut() {
+        projectile.lifetime.tick(time.delta());
+
+        if projectile.lifetime.finished() || !bounds.contains(transform.translation().truncate()) {
+            projectiles.retire(projectile_entity);
+        }
+    }
+}
+
+//the pooled entities went away with the rest of the game
+fn reset_pool(mut commands: Commands) {
+    commands.insert_resource(ProjectilePool::default());
+}
+
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RetireProjectiles;
+
+pub struct ProjectilePlugin;
+
+impl Plugin for ProjectilePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ProjectilePool>()
+            .add_system(reset_pool.in_schedule(OnEnter(AppState::InGame)))
+            .add_systems(
+                (
+                    detonate_projectiles,
+                    cull_projectiles.after(detonate_projectiles),
+                )
+                    .in_set(RetireProjectiles)
+                    .in_set(OnUpdate(AppState::InGame))
+                    .in_set(OnUpdate(PausedState::Playing)),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::*;
+
+    const FRAME: Duration = Duration::from_millis(16);
+
+    //how many get fired this frame, bursts on and off so the pool fills and empties
+    #[derive(Resource)]
+    struct Burst(usize);
+
+    fn fire(mut projectiles: Projectiles, burst: Res<Burst>) {
+        for _ in 0..burst.0 {
+            projectiles.spawn((
+                Projectile::new(
+                    0.5,
+                    0,
+                    Explosion {
+                        radius: 100.0,
+                        strength: 100.0,
+                        damage: 1,
+                    },
+                ),
+                TransformBundle::default(),
+            ));
+        }
+    }
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_event::<CollisionEvent>()
+            .add_event::<ExplosionEvent>()
+            .init_resource::<ProjectilePool>()
+            .insert_resource(Time::default())
+            .insert_resource(Burst(0))
+            .add_systems((fire, detonate_projectiles).chain());
+        app
+    }
+
+    //runs `frames` frames at 60 fps, returns the most entities there were at once
+    fn run(
+        app: &mut App,
+        clock: &mut Instant,
+        frames: usize,
+        burst: impl Fn(usize) -> usize,
+    ) -> usize {
+        let mut most = 0;
+
+        for frame in 0..frames {
+            app.world.resource_mut::<Burst>().0 = burst(frame);
+
+            *clock += FRAME;
+            app.world.resource_mut::<Time>().update_with_instant(*clock);
+            app.update();
+
+            most = most.max(app.world.entities().len() as usize);
+        }
+
+        most
+    }
+
+    #[test]
+    fn firing_for_a_long_time_stays_bounded() {
+        let mut app = app();
+        let mut clock = Instant::now();
+        let bursts = |frame: usize| if frame % 90 < 45 { 20 } else { 0 };
+
+        let warm_up = run(&mut app, &mut clock, 900, bursts);
+        let later = run(&mut app, &mut clock, 9000, bursts);
+
+        //a 0.5 s fuse keeps about 32 frames of bursts in the air at once
+        assert!(warm_up <= 20 * 33 + MAX_POOLED, "{warm_up} entities");
+        assert_eq!(later, warm_up, "kept growing past the warm up");
+
+        //once everything's gone off only the pool is left
+        run(&mut app, &mut clock, 120, |_| 0);
+        assert_eq!(app.world.entities().len() as usize, MAX_POOLED);
+        assert!(app
+            .world
+            .query::<&Projectile>()
+            .iter(&app.world)
+            .next()
+            .is_none());
+    }
+
+    #[test]
+    fn retired_projectiles_get_reused() {
+        let mut app = app();
+        let mut clock = Instant::now();
+
+        let most = run(&mut app, &mut clock, 3000, |_| 1);
+
+        //one flying per frame of fuse, plus the ones sitting in the pool
+        assert!(most <= 33 + 2, "{most} entities");
+    }
+}
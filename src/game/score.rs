@@ -0,0 +1,86 @@
+use bevy::{prelude::*, utils::HashSet};
+
+use crate::{
+    game::{
+        arms::{ArmsTarget, Grabbed},
+        player::PlayerLandedOnEvent,
+        weapon::RocketTargetDestroyed,
+    },
+    AppState,
+};
+
+use super::PausedState;
+
+#[derive(Resource, Default, Debug)]
+pub struct Score {
+    //in pixels above where the player spawned
+    pub max_height: f32,
+    pub platforms_landed: u32,
+    pub enemies_destroyed: u32,
+    pub run_time: f32,
+    last_platform: Option<Entity>,
+}
+
+fn reset_score(mut commands: Commands) {
+    commands.insert_resource(Score::default());
+}
+
+fn track_height(mut score: ResMut<Score>, target_query: Query<&Transform, With<ArmsTarget>>) {
+    let Ok(target) = target_query.get_single() else {
+        return;
+    };
+
+    if target.translation.y > score.max_height {
+        score.max_height = target.translation.y;
+    }
+}
+
+fn track_run_time(
+    mut score: ResMut<Score>,
+    time: Res<Time>,
+    target_query: Query<(), (With<ArmsTarget>, Without<Grabbed>)>,
+) {
+    //the clock stops once the player is grabbed
+    if target_query.is_empty() {
+        return;
+    }
+
+    score.run_time += time.delta_seconds();
+}
+
+fn track_platforms(mut score: ResMut<Score>, mut ev_landed: EventReader<PlayerLandedOnEvent>) {
+    for landed in ev_landed.iter() {
+        //landing on the same platform again after a hop doesn't count
+        if score.last_platform != Some(landed.0) {
+            score.last_platform = Some(landed.0);
+            score.platforms_landed += 1;
+        }
+    }
+}
+
+fn track_arms(mut score: ResMut<Score>, mut ev_destroyed: EventReader<RocketTargetDestroyed>) {
+    //two rockets can hit the same arm in one frame
+    let destroyed = ev_destroyed
+        .iter()
+        .map(|destroyed| destroyed.0)
+        .collect::<HashSet<Entity>>()
+        .len() as u32;
+
+    if destroyed > 0 {
+        score.enemies_destroyed += destroyed;
+    }
+}
+
+pub struct ScorePlugin;
+
+impl Plugin for ScorePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Score>()
+            .add_system(reset_score.in_schedule(OnEnter(AppState::InGame)))
+            .add_systems(
+                (track_height, track_run_time, track_platforms, track_arms)
+                    .in_set(OnUpdate(AppState::InGame))
+                    .in_set(OnUpdate(PausedState::Playing)),
+            );
+    }
+}
@@ -0,0 +1,70 @@
+use bevy::prelude::*;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::AppState;
+
+//what the next run gets seeded with, none picks a fresh one every run
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq)]
+pub struct ChosenSeed(pub Option<u64>);
+
+#[derive(Resource, Default, Debug, Clone, Copy, PartialEq)]
+pub struct RunSeed(pub u64);
+
+//every kind of gameplay randomness draws from its own stream, so the level
+//comes out the same for a seed no matter when arms spawned or what got picked up
+#[derive(Resource)]
+pub struct RunRng {
+    pub platforms: ChaCha8Rng,
+    pub pickups: ChaCha8Rng,
+    pub arms: ChaCha8Rng,
+    pub enemies: ChaCha8Rng,
+}
+
+impl RunRng {
+    pub fn new(seed: u64) -> Self {
+        let stream = |stream: u64| {
+            let mut rng = ChaCha8Rng::seed_from_u64(seed);
+            rng.set_stream(stream);
+            rng
+        };
+
+        RunRng {
+            platforms: stream(0),
+            pickups: stream(1),
+            arms: stream(2),
+            enemies: stream(3),
+        }
+    }
+}
+
+impl Default for RunRng {
+    fn default() -> Self {
+        RunRng::new(0)
+    }
+}
+
+fn start_run(
+    chosen_seed: Res<ChosenSeed>,
+    mut run_seed: ResMut<RunSeed>,
+    mut run_rng: ResMut<RunRng>,
+) {
+    //kept short so it's easy to read off and type back in
+    let seed = chosen_seed
+        .0
+        .unwrap_or_else(|| rand::thread_rng().gen_range(0..1_000_000_000));
+
+    *run_seed = RunSeed(seed);
+    *run_rng = RunRng::new(seed);
+}
+
+pub struct SeedPlugin;
+
+impl Plugin for SeedPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ChosenSeed>()
+            .init_resource::<RunSeed>()
+            .init_resource::<RunRng>()
+            .add_system(start_run.in_schedule(OnEnter(AppState::InGame)));
+    }
+}
@@ -0,0 +1,617 @@
+use std::f32::consts::PI;
+
+use bevy::{
+    ecs::system::SystemParam, input::mouse::MouseWheel, prelude::*, reflect::TypeUuid,
+    sprite::Anchor,
+};
+use bevy_rapier2d::prelude::*;
+use serde::Deserialize;
+
+use crate::{
+    actions::{Action, ActionInput},
+    camera::GameCamera,
+    game::{
+        ammo_pickup::AmmoPickedUp,
+        explosion::Explosion,
+        projectile::{Projectile, Projectiles, RetireProjectiles},
+    },
+    game_audio::{PlaySfx, SfxCue},
+    utils::ron_asset::RonAssetPlugin,
+    AppState,
+};
+
+use super::{GameEntity, PausedState};
+
+const WEAPON_KEYS: [KeyCode; 9] = [
+    KeyCode::Key1,
+    KeyCode::Key2,
+    KeyCode::Key3,
+    KeyCode::Key4,
+    KeyCode::Key5,
+    KeyCode::Key6,
+    KeyCode::Key7,
+    KeyCode::Key8,
+    KeyCode::Key9,
+];
+
+#[derive(Deserialize, Clone, Debug)]
+struct ProjectileDefinition {
+    sprite: String,
+    scale: f32,
+    //collider radius before scaling
+    radius: f32,
+    speed: f32,
+    gravity_scale: f32,
+    restitution: f32,
+    fuse_seconds: f32,
+    //how many things it has to touch to go off, 0 leaves it to the fuse
+    contacts_to_detonate: u32,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+struct AmmoDefinition {
+    magazine: u32,
+    reload_seconds: f32,
+    //spare rounds on top of the first magazine
+    reserve: u32,
+    max_reserve: u32,
+    //rounds added to the reserve by one pickup
+    pickup: u32,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+struct WeaponDefinition {
+    name: String,
+    sprite: String,
+    tint: Color,
+    projectile: ProjectileDefinition,
+    //shots per second
+    fire_rate: f32,
+    pellets: u32,
+    //full angle in degrees the pellets fan out over
+    spread: f32,
+    //none is unlimited
+    ammo: Option<AmmoDefinition>,
+    explosion: Explosion,
+}
+
+//the list in assets/weapons/arsenal.weapons.ron, number keys follow its order
+#[derive(Deserialize, TypeUuid)]
+#[uuid = "436e8137-19e0-4207-b31b-4e861c1394db"]
+struct WeaponDefinitions(Vec<WeaponDefinition>);
+
+#[derive(Resource)]
+struct WeaponDefinitionsHandle(Handle<WeaponDefinitions>);
+
+//the loaded definitions, empty until the file is in
+#[derive(SystemParam)]
+struct Arsenal<'w> {
+    handle: Res<'w, WeaponDefinitionsHandle>,
+    definitions: Res<'w, Assets<WeaponDefinitions>>,
+}
+
+impl<'w> Arsenal<'w> {
+    fn all(&self) -> &[WeaponDefinition] {
+        self.definitions
+            .get(&self.handle.0)
+            .map(|definitions| definitions.0.as_slice())
+            .unwrap_or_default()
+    }
+
+    fn get(&self, index: usize) -> Option<&WeaponDefinition> {
+        self.all().get(index)
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Ammo {
+    loaded: u32,
+    reserve: u32,
+}
+
+impl Ammo {
+    fn full(definition: &AmmoDefinition) -> Self {
+        Ammo {
+            loaded: definition.magazine,
+            reserve: definition.reserve.min(definition.max_reserve),
+        }
+    }
+
+    //what's left once the definition changes, no more than it now holds
+    fn limit_to(self, definition: &AmmoDefinition) -> Self {
+        Ammo {
+            loaded: self.loaded.min(definition.magazine),
+            reserve: self.reserve.min(definition.max_reserve),
+        }
+    }
+}
+
+#[derive(Component)]
+struct Weapon {
+    selected: usize,
+    //the definition the sprite was last set up for
+    shown: Option<usize>,
+    cooldown: Timer,
+    //only ever for the selected weapon, switching away cancels it
+    reload: Option<Timer>,
+    //ammo left per definition, filled in once the definitions are loaded
+    ammo: Vec<Option<Ammo>>,
+}
+
+#[derive(Component)]
+pub struct WeaponHolder;
+
+//what the hud shows about the weapon in hand
+#[derive(Resource, Default, PartialEq)]
+pub struct HeldWeapon {
+    pub name: String,
+    //loaded and spare rounds, none for unlimited
+    pub ammo: Option<(u32, u32)>,
+    //0 to 1 while reloading
+    pub reload_progress: Option<f32>,
+}
+
+fn load_weapon_definitions(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(WeaponDefinitionsHandle(
+        asset_server.load("weapons/arsenal.weapons.ron"),
+    ));
+}
+
+fn spawn_weapon(
+    mut ev_weapon_holder_spawns: EventReader<WeaponHolderSpawns>,
+    mut commands: Commands,
+) {
+    for weapon_holder in ev_weapon_holder_spawns.iter() {
+        //sprite gets filled in by update_held_weapon
+        let child = commands
+            .spawn((
+                Weapon {
+                    selected: 0,
+                    shown: None,
+                    cooldown: Timer::from_seconds(0.0, TimerMode::Once),
+                    reload: None,
+                    ammo: Vec::new(),
+                },
+                SpriteBundle {
+                    sprite: Sprite {
+                        anchor: Anchor::Custom(Vec2::new(0.0, 1.0)),
+                        ..default()
+                    },
+                    transform: Transform {
+                        translation: Vec3::new(0.0, -100.0, 1.0),
+                        scale: Vec3::new(1.0, 1.0, 0.1),
+                        ..default()
+                    },
+                    ..default()
+                },
+                Name::new("Weapon"),
+            ))
+            .id();
+
+        commands
+            .entity(weapon_holder.0)
+            .push_children(&[child])
+            .insert(WeaponHolder);
+    }
+}
+
+fn switch_weapon(
+    actions: ActionInput,
+    keys: Res<Input<KeyCode>>,
+    mut ev_mouse_wheel: EventReader<MouseWheel>,
+    mut weapon_query: Query<&mut Weapon>,
+    arsenal: Arsenal,
+) {
+    let scroll: f32 = ev_mouse_wheel.iter().map(|wheel| wheel.y).sum();
+
+    let Ok(mut weapon) = weapon_query.get_single_mut() else {
+        return;
+    };
+    let count = arsenal.all().len();
+
+    if count == 0 {
+        return;
+    }
+
+    let step = if actions.just_pressed(Action::NextWeapon) || scroll < 0.0 {
+        1
+    } else if actions.just_pressed(Action::PreviousWeapon) || scroll > 0.0 {
+        count - 1
+    } else {
+        0
+    };
+
+    let selected = WEAPON_KEYS
+        .iter()
+        .take(count)
+        .position(|key| keys.just_pressed(*key))
+        .unwrap_or((weapon.selected + step) % count);
+
+    if selected != weapon.selected {
+        weapon.selected = selected;
+        weapon.reload = None;
+    }
+}
+
+//swaps the held sprite and keeps the ammo list in step with the definitions,
+//they can change under us when the file is edited while the game runs
+fn update_held_weapon(
+    mut weapon_query: Query<(&mut Weapon, &mut Handle<Image>, &mut Sprite, &mut Name)>,
+    mut ev_definitions: EventReader<AssetEvent<WeaponDefinitions>>,
+    arsenal: Arsenal,
+    asset_server: Res<AssetServer>,
+) {
+    //counted rather than any(), so every event gets read and none are left for next frame
+    let definitions_changed = ev_definitions
+        .iter()
+        .filter(|ev| {
+            matches!(ev, AssetEvent::Created { handle } | AssetEvent::Modified { handle }
+                if *handle == arsenal.handle.0)
+        })
+        .count()
+        > 0;
+
+    let Ok((mut weapon, mut texture, mut sprite, mut name)) = weapon_query.get_single_mut() else {
+        return;
+    };
+    let definitions = arsenal.all();
+
+    if definitions.is_empty() {
+        return;
+    }
+
+    //an edited file keeps whatever ammo is left, only new weapons start full
+    if weapon.ammo.len() != definitions.len() || definitions_changed {
+        let ammo = definitions
+            .iter()
+            .enumerate()
+            .map(|(index, definition)| {
+                let ammo_definition = definition.ammo.as_ref()?;
+
+                Some(match weapon.ammo.get(index).copied().flatten() {
+                    Some(left) => left.limit_to(ammo_definition),
+                    None => Ammo::full(ammo_definition),
+                })
+            })
+            .collect();
+
+        weapon.ammo = ammo;
+        weapon.reload = None;
+    }
+
+    if weapon.shown == Some(weapon.selected) && !definitions_changed {
+        return;
+    }
+
+    let Some(definition) = definitions.get(weapon.selected) else {
+        weapon.selected = 0;
+        return;
+    };
+
+    weapon.shown = Some(weapon.selected);
+
+    *texture = asset_server.load(definition.sprite.as_str());
+    sprite.color = definition.tint;
+    name.set(definition.name.clone());
+}
+
+fn weapon_aims(
+    actions: ActionInput,
+    mut ev_cursor_moved: EventReader<CursorMoved>,
+    mut aiming_with_stick: Local<bool>,
+    mut weapon_query: Query<&mut Transform, (With<Weapon>, Without<WeaponHolder>)>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<GameCamera>>,
+    weapon_holder_query: Query<&Transform, (With<WeaponHolder>, Without<Weapon>)>,
+) {
+    let Ok(mut weapon) = weapon_query.get_single_mut() else {
+        return;
+    };
+
+    //the stick keeps control until the mouse is touched again
+    if !ev_cursor_moved.is_empty() {
+        ev_cursor_moved.clear();
+        *aiming_with_stick = false;
+    }
+
+    let normalized_delta = if let Some(stick) = actions.aim_stick() {
+        *aiming_with_stick = true;
+        stick
+    } else if *aiming_with_stick {
+        return;
+    } else {
+        let (camera, camera_transform) = camera_query.single();
+
+        let Some(world_position) = actions
+            .aim_cursor()
+            .and_then(|cursor| camera.viewport_to_world(camera_transform, cursor))
+            .map(|ray| ray.origin.truncate())
+        else {
+            return;
+        };
+
+        let weapon_holder = weapon_holder_query.single();
+
+        let delta = Vec2::new(
+            world_position.x - weapon_holder.translation.x,
+            world_position.y - weapon_holder.translation.y,
+        );
+
+        delta.normalize()
+    };
+
+    let angle = normalized_delta.y.atan2(normalized_delta.x);
+
+    weapon.rotation = Quat::from_rotation_z(angle + PI / 2.0);
+}
+
+//what comes out of a weapon when it goes off, the pellets and the bang
+#[derive(SystemParam)]
+struct Gunfire<'w, 's> {
+    projectiles: Projectiles<'w, 's>,
+    asset_server: Res<'w, AssetServer>,
+    ev_sfx: EventWriter<'w, PlaySfx>,
+}
+
+impl<'w, 's> Gunfire<'w, 's> {
+    //from the holder at `origin`, with the weapon turned to `direction_angle`
+    fn fire(&mut self, definition: &WeaponDefinition, origin: Vec3, direction_angle: f32) {
+        let projectile = &definition.projectile;
+        let pellets = definition.pellets.max(1);
+
+        self.ev_sfx
+            .send(PlaySfx::at(SfxCue::Fire, origin.truncate()));
+
+        for pellet in 0..pellets {
+            //fanned out evenly, a single pellet goes straight
+            let spread_offset = if pellets > 1 {
+                (pellet as f32 / (pellets - 1) as f32 - 0.5) * definition.spread.to_radians()
+            } else {
+                0.0
+            };
+
+            let angle = direction_angle + spread_offset;
+            let direction = Vec2::new(f32::sin(angle), -f32::cos(angle));
+
+            self.projectiles.spawn((
+                Projectile::new(
+                    projectile.fuse_seconds,
+                    projectile.contacts_to_detonate,
+                    definition.explosion,
+                ),
+                SpriteBundle {
+                    texture: self.asset_server.load(projectile.sprite.as_str()),
+                    transform: Transform {
+                        translation: Vec3::new(
+                            origin.x + direction.x * 100.0,
+                            origin.y + direction.y * 100.0,
+                            1.0,
+                        ),
+                        scale: Vec3::new(projectile.scale, projectile.scale, 0.1),
+                        ..default()
+                    },
+                    ..default()
+                },
+                RigidBody::Dynamic,
+                Collider::ball(projectile.radius),
+                Restitution {
+                    coefficient: projectile.restitution,
+                    ..default()
+                },
+                ReadMassProperties::default(),
+                GravityScale(projectile.gravity_scale),
+                Velocity {
+                    linvel: direction * projectile.speed,
+                    ..default()
+                },
+                Name::new("Projectile"),
+                ActiveEvents::COLLISION_EVENTS,
+                GameEntity,
+            ));
+        }
+    }
+}
+
+fn weapon_shoots(
+    actions: ActionInput,
+    mut weapon_query: Query<(&Transform, &mut Weapon), Without<WeaponHolder>>,
+    weapon_holder_query: Query<&Transform, (With<WeaponHolder>, Without<Weapon>)>,
+    arsenal: Arsenal,
+    mut gunfire: Gunfire,
+    time: Res<Time>,
+) {
+    let Ok((weapon_transform, mut weapon)) = weapon_query.get_single_mut() else {
+        return;
+    };
+
+    let Ok(weapon_holder_transform) = weapon_holder_query.get_single() else {
+        return;
+    };
+
+    weapon.cooldown.tick(time.delta());
+
+    if !actions.just_pressed(Action::Fire) || !weapon.cooldown.finished() || weapon.reload.is_some()
+    {
+        return;
+    }
+
+    let Some(definition) = arsenal.get(weapon.selected) else {
+        return;
+    };
+
+    let selected = weapon.selected;
+    match weapon.ammo.get_mut(selected) {
+        Some(Some(Ammo { loaded: 0, .. })) | None => return,
+        Some(Some(ammo)) => ammo.loaded -= 1,
+        Some(None) => {}
+    }
+
+    weapon.cooldown = Timer::from_seconds(1.0 / definition.fire_rate, TimerMode::Once);
+
+    let direction_angle = Quat::to_euler(weapon_transform.rotation, EulerRot::XYZ).2;
+
+    gunfire.fire(
+        definition,
+        weapon_holder_transform.translation,
+        direction_angle,
+    );
+}
+
+//starts on an empty magazine or when asked for, moves rounds over from the reserve once done
+fn reload_weapon(
+    actions: ActionInput,
+    mut weapon_query: Query<&mut Weapon>,
+    arsenal: Arsenal,
+    time: Res<Time>,
+) {
+    let Ok(mut weapon) = weapon_query.get_single_mut() else {
+        return;
+    };
+    let weapon = &mut *weapon;
+
+    let Some(ammo_definition) = arsenal
+        .get(weapon.selected)
+        .and_then(|definition| definition.ammo.as_ref())
+    else {
+        return;
+    };
+    let Some(Some(ammo)) = weapon.ammo.get_mut(weapon.selected) else {
+        return;
+    };
+
+    if let Some(reload) = weapon.reload.as_mut() {
+        reload.tick(time.delta());
+
+        if reload.finished() {
+            let moved = ammo_definition
+                .magazine
+                .saturating_sub(ammo.loaded)
+                .min(ammo.reserve);
+            ammo.loaded += moved;
+            ammo.reserve -= moved;
+            weapon.reload = None;
+        }
+
+        return;
+    }
+
+    let wants_reload = ammo.loaded == 0 || actions.just_pressed(Action::Reload);
+
+    if wants_reload && ammo.loaded < ammo_definition.magazine && ammo.reserve > 0 {
+        weapon.reload = Some(Timer::from_seconds(
+            ammo_definition.reload_seconds,
+            TimerMode::Once,
+        ));
+    }
+}
+
+fn collect_ammo(
+    mut ev_ammo_picked_up: EventReader<AmmoPickedUp>,
+    mut weapon_query: Query<&mut Weapon>,
+    arsenal: Arsenal,
+) {
+    let Ok(mut weapon) = weapon_query.get_single_mut() else {
+        return;
+    };
+
+    for _ in ev_ammo_picked_up.iter() {
+        for (ammo, definition) in weapon.ammo.iter_mut().zip(arsenal.all()) {
+            if let (Some(ammo), Some(ammo_definition)) = (ammo, &definition.ammo) {
+                ammo.reserve =
+                    (ammo.reserve + ammo_definition.pickup).min(ammo_definition.max_reserve);
+            }
+        }
+    }
+}
+
+fn update_held_weapon_status(
+    weapon_query: Query<&Weapon>,
+    arsenal: Arsenal,
+    mut held_weapon: ResMut<HeldWeapon>,
+) {
+    let Some((weapon, definition)) = weapon_query
+        .get_single()
+        .ok()
+        .and_then(|weapon| Some((weapon, arsenal.get(weapon.selected)?)))
+    else {
+        return;
+    };
+
+    held_weapon.set_if_neq(HeldWeapon {
+        name: definition.name.clone(),
+        ammo: weapon
+            .ammo
+            .get(weapon.selected)
+            .copied()
+            .flatten()
+            .map(|ammo| (ammo.loaded, ammo.reserve)),
+        reload_progress: weapon.reload.as_ref().map(Timer::percent),
+    });
+}
+
+fn reset_held_weapon(mut commands: Commands) {
+    commands.insert_resource(HeldWeapon::default());
+}
+
+pub struct WeaponHolderSpawns(pub Entity);
+
+//the entity that got despawned, the target's parent if it had one
+pub struct RocketTargetDestroyed(pub Entity);
+
+pub struct WeaponPlugin;
+
+#[derive(Component)]
+pub struct RocketTarget;
+
+impl Plugin for WeaponPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugin(RonAssetPlugin::<WeaponDefinitions>::new(&["weapons.ron"]))
+            .add_startup_system(load_weapon_definitions)
+            .add_systems(
+                (
+                    switch_weapon,
+                    update_held_weapon.after(switch_weapon),
+                    weapon_aims,
+                    weapon_shoots.after(reload_weapon).after(RetireProjectiles),
+                    reload_weapon.after(switch_weapon),
+                    collect_ammo,
+                    update_held_weapon_status.after(weapon_shoots),
+                )
+                    .in_set(OnUpdate(AppState::InGame))
+                    .in_set(OnUpdate(PausedState::Playing)),
+            )
+            .init_resource::<HeldWeapon>()
+            .add_system(reset_held_weapon.in_schedule(OnEnter(AppState::InGame)))
+            .add_system(spawn_weapon.run_if(in_state(AppState::InGame)))
+            .add_event::<WeaponHolderSpawns>()
+            .add_event::<RocketTargetDestroyed>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ammo_definition(magazine: u32, max_reserve: u32) -> AmmoDefinition {
+        AmmoDefinition {
+            magazine,
+            reload_seconds: 1.0,
+            reserve: max_reserve,
+            max_reserve,
+            pickup: 1,
+        }
+    }
+
+    #[test]
+    fn edited_definitions_keep_what_is_left() {
+        let left = Ammo {
+            loaded: 2,
+            reserve: 5,
+        };
+
+        let bigger = left.limit_to(&ammo_definition(10, 30));
+        assert_eq!((bigger.loaded, bigger.reserve), (2, 5));
+
+        let smaller = left.limit_to(&ammo_definition(1, 3));
+        assert_eq!((smaller.loaded, smaller.reserve), (1, 3));
+    }
+}
@@ -0,0 +1,133 @@
+mod music;
+mod sfx;
+mod spatial;
+
+use bevy::{prelude::*, window::WindowFocused};
+use bevy_kira_audio::*;
+use serde::{Deserialize, Serialize};
+
+use crate::persistence::{PersistPlugin, Persistent};
+
+pub use self::sfx::{PlaySfx, SfxCue};
+
+#[derive(Resource)]
+struct MusicChannel;
+
+#[derive(Resource)]
+struct SFXChannel;
+
+//music volume is mixed per layer by the music director
+fn update_volume(game_audio_settings: Res<GameAudioSettings>, sfx: Res<AudioChannel<SFXChannel>>) {
+    if game_audio_settings.is_changed() || game_audio_settings.is_added() {
+        sfx.set_volume(game_audio_settings.sfx_volume());
+    }
+}
+
+//everything stops where it is while the window is in the background, and picks up again after
+fn mute_when_unfocused(
+    mut ev_focused: EventReader<WindowFocused>,
+    game_audio_settings: Res<GameAudioSettings>,
+    sfx: Res<AudioChannel<SFXChannel>>,
+    music: Res<AudioChannel<MusicChannel>>,
+    mut muted: Local<bool>,
+) {
+    for focused in ev_focused.iter() {
+        if !focused.focused && game_audio_settings.mute_when_unfocused && !*muted {
+            sfx.pause();
+            music.pause();
+            *muted = true;
+        } else if focused.focused && *muted {
+            sfx.resume();
+            music.resume();
+            *muted = false;
+        }
+    }
+}
+
+//fields added after the first version have defaults so older files still load
+#[derive(Resource, Debug, Serialize, Deserialize)]
+pub struct GameAudioSettings {
+    pub master: f64,
+    pub music: f64,
+    pub sfx: f64,
+    #[serde(default)]
+    pub music_muted: bool,
+    #[serde(default)]
+    pub sfx_muted: bool,
+    //pan and fade sounds by where they are on screen
+    #[serde(default = "default_true")]
+    pub positional: bool,
+    #[serde(default = "default_true")]
+    pub mute_when_unfocused: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl GameAudioSettings {
+    pub fn music_volume(&self) -> f64 {
+        if self.music_muted {
+            0.0
+        } else {
+            self.music * self.master
+        }
+    }
+
+    pub fn sfx_volume(&self) -> f64 {
+        if self.sfx_muted {
+            0.0
+        } else {
+            self.sfx * self.master
+        }
+    }
+}
+
+impl Default for GameAudioSettings {
+    fn default() -> Self {
+        GameAudioSettings {
+            master: 1.0,
+            music: 1.0,
+            sfx: 1.0,
+            music_muted: false,
+            sfx_muted: false,
+            positional: true,
+            mute_when_unfocused: true,
+        }
+    }
+}
+
+impl Persistent for GameAudioSettings {
+    const FILE_NAME: &'static str = "audio_settings.ron";
+    const VERSION: u32 = 1;
+
+    //a hand edited 7.0 or -1.0 would go straight to the audio channels
+    fn sanitize(&mut self) {
+        let defaults = GameAudioSettings::default();
+        let volume = |volume: f64, default: f64| {
+            if volume.is_finite() {
+                volume.clamp(0.0, 1.0)
+            } else {
+                default
+            }
+        };
+
+        self.master = volume(self.master, defaults.master);
+        self.music = volume(self.music, defaults.music);
+        self.sfx = volume(self.sfx, defaults.sfx);
+    }
+}
+
+pub(super) struct GameAudioPlugin;
+
+impl Plugin for GameAudioPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugin(PersistPlugin::<GameAudioSettings>::default())
+            .add_system(update_volume)
+            .add_system(mute_when_unfocused)
+            .add_audio_channel::<MusicChannel>()
+            .add_audio_channel::<SFXChannel>()
+            .add_plugin(sfx::SfxPlugin)
+            .add_plugin(music::MusicPlugin);
+    }
+}
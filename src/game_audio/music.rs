@@ -0,0 +1,246 @@
+use std::time::Duration;
+
+use bevy::{ecs::system::SystemParam, prelude::*, reflect::TypeUuid};
+use bevy_kira_audio::prelude::*;
+use serde::Deserialize;
+
+use crate::{
+    game::{arms::Arms, PausedState},
+    utils::ron_asset::RonAssetPlugin,
+    AppState,
+};
+
+use super::{GameAudioSettings, MusicChannel};
+
+//how long a layer takes to settle on a new volume, so arms coming and going swell in and out
+const MIX_SECONDS: f32 = 0.75;
+
+//smaller volume changes than this aren't worth sending
+const MIX_THRESHOLD: f64 = 0.005;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum MusicMood {
+    Menu,
+    Game,
+}
+
+impl MusicMood {
+    fn for_state(state: AppState) -> Self {
+        match state {
+            AppState::InGame => MusicMood::Game,
+            AppState::MainMenu | AppState::GameOver | AppState::HighScores | AppState::Editor => {
+                MusicMood::Menu
+            }
+        }
+    }
+}
+
+//one file of a track, every layer of a track starts together so they stay in time
+#[derive(Deserialize, Clone, Debug)]
+struct MusicLayer {
+    path: String,
+    #[serde(default)]
+    start_from: f64,
+    //where it jumps back to after the end, in seconds
+    #[serde(default)]
+    loop_from: f64,
+    #[serde(default = "default_volume")]
+    volume: f64,
+    //none plays all the time, otherwise it fades in with each arm until this many are up
+    #[serde(default)]
+    full_at_arms: Option<usize>,
+}
+
+fn default_volume() -> f64 {
+    1.0
+}
+
+#[derive(Deserialize, Clone, Debug)]
+struct MusicTrack {
+    layers: Vec<MusicLayer>,
+}
+
+//assets/audio/soundtrack.music.ron
+#[derive(Deserialize, TypeUuid, Debug)]
+#[uuid = "4d8b2f6a-91c3-4e57-a2d0-6f3e8c1b7a94"]
+struct MusicDefinitions {
+    menu: MusicTrack,
+    game: MusicTrack,
+    crossfade_seconds: f32,
+    //what's left of the music while the game is paused
+    paused_volume: f64,
+}
+
+impl MusicDefinitions {
+    fn track(&self, mood: MusicMood) -> &MusicTrack {
+        match mood {
+            MusicMood::Menu => &self.menu,
+            MusicMood::Game => &self.game,
+        }
+    }
+}
+
+#[derive(Resource)]
+struct MusicDefinitionsHandle(Handle<MusicDefinitions>);
+
+#[derive(SystemParam)]
+struct Soundtrack<'w> {
+    handle: Res<'w, MusicDefinitionsHandle>,
+    definitions: Res<'w, Assets<MusicDefinitions>>,
+}
+
+impl<'w> Soundtrack<'w> {
+    fn get(&self) -> Option<&MusicDefinitions> {
+        self.definitions.get(&self.handle.0)
+    }
+}
+
+//everything a layer's volume depends on besides the layer itself
+#[derive(SystemParam)]
+struct MusicMix<'w, 's> {
+    settings: Res<'w, GameAudioSettings>,
+    app_state: Res<'w, State<AppState>>,
+    paused_state: Res<'w, State<PausedState>>,
+    arms: Query<'w, 's, (), With<Arms>>,
+}
+
+impl<'w, 's> MusicMix<'w, 's> {
+    //the channel volume would be overwritten by every layer's own, so the sliders go in here
+    fn volume(&self, definitions: &MusicDefinitions, layer: &MusicLayer) -> f64 {
+        let intensity = match layer.full_at_arms {
+            None => 1.0,
+            Some(full_at_arms) => {
+                (self.arms.iter().len() as f64 / full_at_arms.max(1) as f64).min(1.0)
+            }
+        };
+
+        let paused =
+            if self.app_state.0 == AppState::InGame && self.paused_state.0 == PausedState::Paused {
+                definitions.paused_volume
+            } else {
+                1.0
+            };
+
+        layer.volume * intensity * paused * self.settings.music_volume()
+    }
+}
+
+struct PlayingLayer {
+    handle: Handle<AudioInstance>,
+    //what it was last told to play at
+    volume: f64,
+}
+
+//the mood that's playing and its layers, in the same order as the track's definition
+#[derive(Resource, Default)]
+struct MusicDirector {
+    mood: Option<MusicMood>,
+    layers: Vec<PlayingLayer>,
+}
+
+fn load_music_definitions(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(MusicDefinitionsHandle(
+        asset_server.load("audio/soundtrack.music.ron"),
+    ));
+}
+
+//fades the old track out while the one for the new state fades in
+fn switch_tracks(
+    mut ev_definitions: EventReader<AssetEvent<MusicDefinitions>>,
+    soundtrack: Soundtrack,
+    mix: MusicMix,
+    mut director: ResMut<MusicDirector>,
+    music_channel: Res<AudioChannel<MusicChannel>>,
+    mut audio_instances: ResMut<Assets<AudioInstance>>,
+    asset_server: Res<AssetServer>,
+) {
+    //an edited file starts over with the new layers
+    if ev_definitions
+        .iter()
+        .any(|ev| matches!(ev, AssetEvent::Modified { .. }))
+    {
+        director.mood = None;
+    }
+
+    let mood = MusicMood::for_state(mix.app_state.0);
+    if director.mood == Some(mood) {
+        return;
+    }
+
+    let Some(definitions) = soundtrack.get() else {
+        return;
+    };
+
+    let crossfade = AudioTween::linear(Duration::from_secs_f32(definitions.crossfade_seconds));
+
+    for layer in director.layers.drain(..) {
+        if let Some(instance) = audio_instances.get_mut(&layer.handle) {
+            instance.stop(crossfade.clone());
+        }
+    }
+
+    director.layers = definitions
+        .track(mood)
+        .layers
+        .iter()
+        .map(|layer| {
+            let volume = mix.volume(definitions, layer);
+
+            let handle = music_channel
+                .play(asset_server.load(layer.path.as_str()))
+                .start_from(layer.start_from)
+                .loop_from(layer.loop_from)
+                .with_volume(volume)
+                .fade_in(crossfade.clone())
+                .handle();
+
+            PlayingLayer { handle, volume }
+        })
+        .collect();
+
+    director.mood = Some(mood);
+}
+
+fn mix_music(
+    soundtrack: Soundtrack,
+    mix: MusicMix,
+    mut director: ResMut<MusicDirector>,
+    mut audio_instances: ResMut<Assets<AudioInstance>>,
+) {
+    let (Some(definitions), Some(mood)) = (soundtrack.get(), director.mood) else {
+        return;
+    };
+
+    let tween = AudioTween::linear(Duration::from_secs_f32(MIX_SECONDS));
+
+    for (playing, layer) in director
+        .layers
+        .iter_mut()
+        .zip(&definitions.track(mood).layers)
+    {
+        let volume = mix.volume(definitions, layer);
+        if (volume - playing.volume).abs() < MIX_THRESHOLD {
+            continue;
+        }
+
+        //not started yet, it'll get picked up on a later frame
+        let Some(instance) = audio_instances.get_mut(&playing.handle) else {
+            continue;
+        };
+
+        instance.set_volume(volume, tween.clone());
+        playing.volume = volume;
+    }
+}
+
+pub(super) struct MusicPlugin;
+
+impl Plugin for MusicPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugin(RonAssetPlugin::<MusicDefinitions>::new(&["music.ron"]))
+            .init_resource::<MusicDirector>()
+            .add_startup_system(load_music_definitions)
+            .add_system(switch_tracks)
+            .add_system(mix_music.after(switch_tracks));
+    }
+}
@@ -0,0 +1,258 @@
+use std::collections::HashMap;
+
+use bevy::{ecs::system::SystemParam, prelude::*, reflect::TypeUuid};
+use bevy_kira_audio::prelude::*;
+use rand::Rng;
+use serde::Deserialize;
+
+use crate::utils::ron_asset::RonAssetPlugin;
+
+use super::{
+    spatial::{Listener, SfxEmitter},
+    SFXChannel,
+};
+
+//everything the game can make a noise for, the manifest says what each one sounds like
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SfxCue {
+    Fire,
+    Explode,
+    Jump,
+    Land,
+    Grab,
+    ArmRise,
+    ButtonHover,
+    ButtonClick,
+}
+
+pub struct PlaySfx {
+    pub cue: SfxCue,
+    //none for sounds that aren't anywhere in the world, like menu clicks
+    pub emitter: Option<SfxEmitter>,
+}
+
+impl PlaySfx {
+    pub fn flat(cue: SfxCue) -> Self {
+        PlaySfx { cue, emitter: None }
+    }
+
+    pub fn at(cue: SfxCue, position: Vec2) -> Self {
+        PlaySfx {
+            cue,
+            emitter: Some(SfxEmitter::At(position)),
+        }
+    }
+
+    pub fn following(cue: SfxCue, entity: Entity) -> Self {
+        PlaySfx {
+            cue,
+            emitter: Some(SfxEmitter::Following(entity)),
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+struct SfxDefinition {
+    //one of these gets picked each time the cue plays
+    sounds: Vec<String>,
+    #[serde(default = "default_volume")]
+    volume: f64,
+    //how far off the volume and pitch can randomly be, 0.1 is up to 10% either way
+    #[serde(default)]
+    volume_variation: f64,
+    #[serde(default)]
+    pitch_variation: f64,
+    //any more at once than this and the new ones are dropped
+    #[serde(default = "default_max_concurrent")]
+    max_concurrent: usize,
+}
+
+fn default_volume() -> f64 {
+    1.0
+}
+
+fn default_max_concurrent() -> usize {
+    4
+}
+
+//assets/audio/manifest.sfx.ron
+#[derive(Deserialize, TypeUuid, Debug)]
+#[uuid = "c13e5b86-2f47-4a9d-8e60-7b1d94f2a3c5"]
+struct SfxManifest(HashMap<SfxCue, SfxDefinition>);
+
+#[derive(Resource)]
+struct SfxManifestHandle(Handle<SfxManifest>);
+
+//the sounds of each cue, loaded up front so the first play isn't late
+#[derive(Resource, Default)]
+struct SfxSources(HashMap<SfxCue, Vec<Handle<AudioSource>>>);
+
+struct PlayingInstance {
+    handle: Handle<AudioInstance>,
+    cue: SfxCue,
+    //the cue's own volume, before the sliders and distance
+    volume: f64,
+    emitter: Option<SfxEmitter>,
+    last_position: Option<Vec2>,
+}
+
+#[derive(Resource, Default)]
+struct PlayingSfx(Vec<PlayingInstance>);
+
+//the loaded manifest and the sounds for each cue in it
+#[derive(SystemParam)]
+struct SfxLibrary<'w> {
+    manifest_handle: Res<'w, SfxManifestHandle>,
+    manifests: Res<'w, Assets<SfxManifest>>,
+    sources: Res<'w, SfxSources>,
+}
+
+impl<'w> SfxLibrary<'w> {
+    fn get(&self, cue: SfxCue) -> Option<(&SfxDefinition, &[Handle<AudioSource>])> {
+        let manifest = self.manifests.get(&self.manifest_handle.0)?;
+
+        match (manifest.0.get(&cue), self.sources.0.get(&cue)) {
+            (Some(definition), Some(sources)) if !sources.is_empty() => {
+                Some((definition, sources.as_slice()))
+            }
+            _ => None,
+        }
+    }
+}
+
+fn load_sfx_manifest(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(SfxManifestHandle(
+        asset_server.load("audio/manifest.sfx.ron"),
+    ));
+}
+
+fn load_sfx_sources(
+    mut ev_manifests: EventReader<AssetEvent<SfxManifest>>,
+    manifests: Res<Assets<SfxManifest>>,
+    mut sources: ResMut<SfxSources>,
+    asset_server: Res<AssetServer>,
+) {
+    for ev_manifest in ev_manifests.iter() {
+        let (AssetEvent::Created { handle } | AssetEvent::Modified { handle }) = ev_manifest else {
+            continue;
+        };
+
+        let Some(manifest) = manifests.get(handle) else {
+            continue;
+        };
+
+        sources.0 = manifest
+            .0
+            .iter()
+            .map(|(cue, definition)| {
+                let sounds = definition
+                    .sounds
+                    .iter()
+                    .map(|sound| asset_server.load(sound.as_str()))
+                    .collect();
+
+                (*cue, sounds)
+            })
+            .collect();
+    }
+}
+
+//the channel volume only covers sounds already playing, so new ones get it baked in
+fn play_sfx(
+    mut ev_play: EventReader<PlaySfx>,
+    library: SfxLibrary,
+    mut playing: ResMut<PlayingSfx>,
+    sfx_channel: Res<AudioChannel<SFXChannel>>,
+    listener: Listener,
+) {
+    playing
+        .0
+        .retain(|instance| sfx_channel.state(&instance.handle) != PlaybackState::Stopped);
+
+    let mut rng = rand::thread_rng();
+
+    for play in ev_play.iter() {
+        let Some((definition, sources)) = library.get(play.cue) else {
+            warn!("no sound for {:?} in the sfx manifest", play.cue);
+            continue;
+        };
+
+        let already_playing = playing
+            .0
+            .iter()
+            .filter(|instance| instance.cue == play.cue)
+            .count();
+        if already_playing >= definition.max_concurrent {
+            continue;
+        }
+
+        let source = sources[rng.gen_range(0..sources.len())].clone();
+
+        let vary = |rng: &mut rand::rngs::ThreadRng, variation: f64| {
+            1.0 + rng.gen_range(-variation..=variation)
+        };
+
+        let volume = (definition.volume * vary(&mut rng, definition.volume_variation)).max(0.0);
+        let position = play.emitter.and_then(|emitter| listener.locate(emitter));
+        let (distance_volume, panning) = listener.hear(position);
+
+        let handle = sfx_channel
+            .play(source)
+            .with_volume(volume * listener.gain() * distance_volume)
+            .with_panning(panning)
+            .with_playback_rate(vary(&mut rng, definition.pitch_variation).max(0.1))
+            .handle();
+
+        playing.0.push(PlayingInstance {
+            handle,
+            cue: play.cue,
+            volume,
+            emitter: play.emitter,
+            last_position: position,
+        });
+    }
+}
+
+//sounds out in the world follow their emitter and the camera around while they play
+fn mix_positional_sfx(
+    mut playing: ResMut<PlayingSfx>,
+    listener: Listener,
+    mut audio_instances: ResMut<Assets<AudioInstance>>,
+) {
+    for instance in playing.0.iter_mut() {
+        let Some(emitter) = instance.emitter else {
+            continue;
+        };
+
+        if let Some(position) = listener.locate(emitter) {
+            instance.last_position = Some(position);
+        }
+
+        let Some(audio_instance) = audio_instances.get_mut(&instance.handle) else {
+            continue;
+        };
+
+        let (distance_volume, panning) = listener.hear(instance.last_position);
+
+        audio_instance.set_volume(
+            instance.volume * listener.gain() * distance_volume,
+            AudioTween::default(),
+        );
+        audio_instance.set_panning(panning, AudioTween::default());
+    }
+}
+
+pub(super) struct SfxPlugin;
+
+impl Plugin for SfxPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugin(RonAssetPlugin::<SfxManifest>::new(&["sfx.ron"]))
+            .add_event::<PlaySfx>()
+            .init_resource::<SfxSources>()
+            .init_resource::<PlayingSfx>()
+            .add_startup_system(load_sfx_manifest)
+            .add_system(load_sfx_sources)
+            .add_system(play_sfx.after(load_sfx_sources))
+            .add_system(mix_positional_sfx.after(play_sfx));
+    }
+}
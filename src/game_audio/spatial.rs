@@ -0,0 +1,72 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+use crate::camera::GameCamera;
+
+use super::GameAudioSettings;
+
+//anything closer than this to the middle of the screen is at full volume,
+//it fades out from there and is gone by the silent distance
+const FULL_VOLUME_DISTANCE: f32 = 600.0;
+const SILENT_DISTANCE: f32 = 3000.0;
+
+//this far off to one side a sound is as panned as it gets
+const FULL_PAN_DISTANCE: f32 = 900.0;
+
+//panning goes from 0 (left) to 1 (right), this keeps some of it in the other ear
+const MAX_PAN: f64 = 0.35;
+
+//where a sound comes from in the world
+#[derive(Clone, Copy, Debug)]
+pub enum SfxEmitter {
+    At(Vec2),
+    //keeps up with the entity while the sound plays, stays where it was last seen once it's gone
+    Following(Entity),
+}
+
+//how a sound at some position is heard from the camera
+#[derive(SystemParam)]
+pub(super) struct Listener<'w, 's> {
+    camera: Query<'w, 's, &'static GlobalTransform, With<GameCamera>>,
+    transforms: Query<'w, 's, &'static GlobalTransform>,
+    settings: Res<'w, GameAudioSettings>,
+}
+
+impl<'w, 's> Listener<'w, 's> {
+    pub fn locate(&self, emitter: SfxEmitter) -> Option<Vec2> {
+        match emitter {
+            SfxEmitter::At(position) => Some(position),
+            SfxEmitter::Following(entity) => self
+                .transforms
+                .get(entity)
+                .ok()
+                .map(|transform| transform.translation().truncate()),
+        }
+    }
+
+    //the slider volume everything on the sfx channel gets
+    pub fn gain(&self) -> f64 {
+        self.settings.sfx_volume()
+    }
+
+    //volume factor and panning, sounds without a position play flat in the middle
+    pub fn hear(&self, position: Option<Vec2>) -> (f64, f64) {
+        let (Some(position), Ok(camera)) = (position, self.camera.get_single()) else {
+            return (1.0, 0.5);
+        };
+
+        if !self.settings.positional {
+            return (1.0, 0.5);
+        }
+
+        let offset = position - camera.translation().truncate();
+
+        let fade =
+            (offset.length() - FULL_VOLUME_DISTANCE) / (SILENT_DISTANCE - FULL_VOLUME_DISTANCE);
+        let volume = (1.0 - fade.clamp(0.0, 1.0)).powi(2);
+
+        let side = (offset.x / FULL_PAN_DISTANCE).clamp(-1.0, 1.0) as f64;
+        let panning = 0.5 + side * MAX_PAN;
+
+        (volume as f64, panning)
+    }
+}
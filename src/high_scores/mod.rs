@@ -0,0 +1,366 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    persistence::{PersistPlugin, Persistent},
+    utils::interact_button::{self, HoverButton},
+    AppState, PIXELS_PER_METER,
+};
+
+const MAX_ENTRIES: usize = 10;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HighScoreEntry {
+    pub max_height: f32,
+    //files from before other enemies were counted too still load
+    #[serde(alias = "arms_destroyed")]
+    pub enemies_destroyed: u32,
+    pub run_time: f32,
+    //seconds since the unix epoch
+    pub date: u64,
+}
+
+impl HighScoreEntry {
+    pub fn new(max_height: f32, enemies_destroyed: u32, run_time: f32) -> Self {
+        HighScoreEntry {
+            max_height,
+            enemies_destroyed,
+            run_time,
+            date: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|since_epoch| since_epoch.as_secs())
+                .unwrap_or(0),
+        }
+    }
+}
+
+//best height first
+#[derive(Resource, Debug, Default, Serialize, Deserialize)]
+pub struct HighScores {
+    entries: Vec<HighScoreEntry>,
+}
+
+impl HighScores {
+    //returns where the entry ended up, none if it didn't make the table
+    pub fn insert(&mut self, entry: HighScoreEntry) -> Option<usize> {
+        let rank = self
+            .entries
+            .iter()
+            .position(|existing| entry.max_height > existing.max_height)
+            .unwrap_or(self.entries.len());
+
+        if rank >= MAX_ENTRIES {
+            return None;
+        }
+
+        self.entries.insert(rank, entry);
+        self.entries.truncate(MAX_ENTRIES);
+
+        Some(rank)
+    }
+}
+
+impl Persistent for HighScores {
+    const FILE_NAME: &'static str = "high_scores.ron";
+    const VERSION: u32 = 1;
+
+    fn sanitize(&mut self) {
+        self.entries.retain(|entry| {
+            entry.max_height.is_finite()
+                && entry.max_height >= 0.0
+                && entry.run_time.is_finite()
+                && entry.run_time >= 0.0
+        });
+        self.entries
+            .sort_by(|a, b| b.max_height.total_cmp(&a.max_height));
+        self.entries.truncate(MAX_ENTRIES);
+    }
+}
+
+pub fn format_run_time(seconds: f32) -> String {
+    let seconds = seconds as u32;
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+//yyyy-mm-dd in utc, see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn format_date(seconds_since_epoch: u64) -> String {
+    let days = (seconds_since_epoch / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+#[derive(Component)]
+struct HighScoresMenu;
+
+#[derive(Component)]
+struct BackButton;
+
+impl HoverButton for BackButton {
+    fn on_click(commands: &mut Commands) {
+        commands.insert_resource(NextState(Some(AppState::MainMenu)));
+    }
+}
+
+fn create_high_scores_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    high_scores: Res<HighScores>,
+) {
+    let text_style = TextStyle {
+        font: asset_server.load("century-gothic/gothic.ttf"),
+        font_size: 25.0,
+        color: Color::WHITE,
+    };
+
+    commands
+        .spawn((
+            NodeBundle {
+                background_color: BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.5)),
+                style: Style {
+                    size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                    position_type: PositionType::Absolute,
+                    display: Display::Flex,
+                    align_items: AlignItems::Center,
+                    flex_direction: FlexDirection::Column,
+                    ..default()
+                },
+                ..default()
+            },
+            HighScoresMenu,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        display: Display::Flex,
+                        size: Size::width(Val::Percent(100.0)),
+                        justify_content: JustifyContent::SpaceBetween,
+                        align_items: AlignItems::Center,
+                        padding: UiRect::horizontal(Val::Px(10.0)),
+                        ..default()
+                    },
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        ButtonBundle {
+                            style: Style {
+                                size: Size::new(Val::Px(30.0), Val::Px(56.2)),
+                                ..default()
+                            },
+                            image: asset_server.load("menus/buttons/back_arrow.png").into(),
+                            ..default()
+                        },
+                        BackButton,
+                    ));
+
+                    parent.spawn(
+                        TextBundle::from_section(
+                            "High scores",
+                            TextStyle {
+                                font: asset_server.load("century-gothic/gothic.ttf"),
+                                font_size: 60.0,
+                                color: Color::WHITE,
+                            },
+                        )
+                        .with_style(Style {
+                            margin: UiRect::all(Val::Px(10.0)),
+                            ..default()
+                        }),
+                    );
+
+                    parent.spawn(NodeBundle::default());
+                });
+
+            if high_scores.entries.is_empty() {
+                parent.spawn(TextBundle::from_section("No runs yet", text_style.clone()));
+            }
+
+            for (rank, entry) in high_scores.entries.iter().enumerate() {
+                parent.spawn(TextBundle::from_section(
+                    format!(
+                        "#{}   {}m   {} enemies   {}   {}",
+                        rank + 1,
+                        (entry.max_height / PIXELS_PER_METER) as i32,
+                        entry.enemies_destroyed,
+                        format_run_time(entry.run_time),
+                        format_date(entry.date),
+                    ),
+                    text_style.clone(),
+                ));
+            }
+        });
+}
+
+fn despawn_high_scores_menu(mut commands: Commands, menu: Query<Entity, With<HighScoresMenu>>) {
+    if let Ok(menu_entity) = menu.get_single() {
+        commands.entity(menu_entity).despawn_recursive();
+    }
+}
+
+pub struct HighScoresPlugin;
+
+impl Plugin for HighScoresPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugin(PersistPlugin::<HighScores>::default())
+            .add_system(create_high_scores_menu.in_schedule(OnEnter(AppState::HighScores)))
+            .add_system(despawn_high_scores_menu.in_schedule(OnExit(AppState::HighScores)))
+            .add_system(
+                interact_button::interact_system::<BackButton>
+                    .in_set(OnUpdate(AppState::HighScores)),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::persistence::{file_path, try_load, TempDir};
+
+    fn entry(max_height: f32) -> HighScoreEntry {
+        HighScoreEntry {
+            max_height,
+            enemies_destroyed: 3,
+            run_time: 60.0,
+            date: 0,
+        }
+    }
+
+    fn heights(high_scores: &HighScores) -> Vec<f32> {
+        high_scores
+            .entries
+            .iter()
+            .map(|entry| entry.max_height)
+            .collect()
+    }
+
+    fn full_table() -> HighScores {
+        let mut high_scores = HighScores::default();
+        for i in 0..MAX_ENTRIES {
+            high_scores.insert(entry(1000.0 * (i + 1) as f32));
+        }
+        high_scores
+    }
+
+    #[test]
+    fn best_height_first() {
+        let mut high_scores = HighScores::default();
+
+        assert_eq!(high_scores.insert(entry(500.0)), Some(0));
+        assert_eq!(high_scores.insert(entry(2000.0)), Some(0));
+        assert_eq!(high_scores.insert(entry(1000.0)), Some(1));
+        assert_eq!(high_scores.insert(entry(100.0)), Some(3));
+
+        assert_eq!(heights(&high_scores), [2000.0, 1000.0, 500.0, 100.0]);
+    }
+
+    #[test]
+    fn ties_go_below_the_older_entry() {
+        let mut high_scores = HighScores::default();
+        high_scores.insert(entry(1000.0));
+
+        let mut tie = entry(1000.0);
+        tie.date = 1;
+
+        assert_eq!(high_scores.insert(tie), Some(1));
+        assert_eq!(high_scores.entries[0].date, 0);
+    }
+
+    #[test]
+    fn keeps_only_the_top_entries() {
+        let mut high_scores = full_table();
+
+        assert_eq!(high_scores.insert(entry(1.0)), None);
+        assert_eq!(high_scores.entries.len(), MAX_ENTRIES);
+
+        assert_eq!(high_scores.insert(entry(5500.0)), Some(5));
+        assert_eq!(high_scores.entries.len(), MAX_ENTRIES);
+        assert_eq!(high_scores.entries.last().unwrap().max_height, 2000.0);
+    }
+
+    #[test]
+    fn sanitizing_drops_bad_entries_and_reorders() {
+        let mut high_scores = HighScores {
+            entries: [300.0, f32::NAN, -50.0, f32::INFINITY, 900.0, 0.0, 600.0]
+                .map(entry)
+                .to_vec(),
+        };
+
+        let mut bad_time = entry(700.0);
+        bad_time.run_time = -1.0;
+        high_scores.entries.push(bad_time);
+
+        let mut bad_time = entry(800.0);
+        bad_time.run_time = f32::NAN;
+        high_scores.entries.push(bad_time);
+
+        high_scores.sanitize();
+
+        assert_eq!(heights(&high_scores), [900.0, 600.0, 300.0, 0.0]);
+    }
+
+    #[test]
+    fn sanitizing_truncates_a_long_table() {
+        let mut high_scores = HighScores {
+            entries: (0..MAX_ENTRIES * 3).map(|i| entry(i as f32)).collect(),
+        };
+
+        high_scores.sanitize();
+
+        assert_eq!(high_scores.entries.len(), MAX_ENTRIES);
+        assert_eq!(
+            high_scores.entries[0].max_height,
+            (MAX_ENTRIES * 3 - 1) as f32
+        );
+    }
+
+    #[test]
+    fn hand_edited_file_is_sanitized_on_load() {
+        let dir = TempDir::new("high-scores-edited");
+        fs::write(
+            file_path::<HighScores>(&dir.0),
+            "(version: 1, data: (entries: [
+                (max_height: 10.0, enemies_destroyed: 0, run_time: 5.0, date: 0),
+                (max_height: -3.0, enemies_destroyed: 0, run_time: 5.0, date: 0),
+                (max_height: 99.0, enemies_destroyed: 2, run_time: inf, date: 0),
+                (max_height: 40.0, enemies_destroyed: 1, run_time: 9.0, date: 0),
+            ]))",
+        )
+        .unwrap();
+
+        let high_scores = try_load::<HighScores>(&dir.0).unwrap();
+        assert_eq!(heights(&high_scores), [40.0, 10.0]);
+    }
+
+    #[test]
+    fn older_files_still_load() {
+        let dir = TempDir::new("high-scores-older");
+        fs::write(
+            file_path::<HighScores>(&dir.0),
+            "(version: 1, data: (entries: [
+                (max_height: 10.0, arms_destroyed: 4, run_time: 5.0, date: 0),
+            ]))",
+        )
+        .unwrap();
+
+        let high_scores = try_load::<HighScores>(&dir.0).unwrap();
+        assert_eq!(high_scores.entries[0].enemies_destroyed, 4);
+    }
+}
@@ -4,19 +4,28 @@ use bevy_kira_audio::prelude::*;
 use bevy_rapier2d::prelude::*;
 use game_audio::GameAudioPlugin;
 
+mod actions;
 mod background;
 mod camera;
+mod editor;
 mod game;
 mod game_audio;
+mod high_scores;
 mod main_menu;
+mod persistence;
 mod settings_menu;
 mod utils;
 
+pub const PIXELS_PER_METER: f32 = 100.0;
+
 #[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, States)]
 pub enum AppState {
     #[default]
     MainMenu,
     InGame,
+    GameOver,
+    HighScores,
+    Editor,
 }
 
 #[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, States)]
@@ -31,7 +40,7 @@ fn main() {
         //Default plugins
         .add_plugins(DefaultPlugins)
         //Rapier
-        .add_plugin(RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(100.0))
+        .add_plugin(RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(PIXELS_PER_METER))
         .add_plugin(RapierDebugRenderPlugin::default())
         .add_system(setup_rapier)
         //World inspector
@@ -39,6 +48,9 @@ fn main() {
         //Audio
         .add_plugin(AudioPlugin)
         .add_plugin(GameAudioPlugin)
+        //Input
+        .add_plugin(actions::ActionsPlugin)
+        .add_plugin(utils::gamepad_navigation::GamepadNavigationPlugin)
         //App state
         .add_state::<AppState>()
         .add_state::<SettingsState>()
@@ -48,6 +60,8 @@ fn main() {
         .add_plugin(main_menu::MenuPlugin)
         .add_plugin(background::BackgroundPlugin)
         .add_plugin(settings_menu::SettingsPlugin)
+        .add_plugin(high_scores::HighScoresPlugin)
+        .add_plugin(editor::EditorPlugin)
         //run
         .run();
 }
@@ -0,0 +1,86 @@
+use bevy::prelude::*;
+
+use crate::game::difficulty::Difficulty;
+
+#[derive(Component)]
+pub(super) struct DifficultyButton;
+
+#[derive(Component)]
+pub(super) struct DifficultyText;
+
+fn difficulty_label(difficulty: Difficulty) -> String {
+    format!("Difficulty: {}", difficulty.label())
+}
+
+pub(super) fn create_difficulty_picker(
+    parent: &mut ChildBuilder,
+    asset_server: &AssetServer,
+    difficulty: Difficulty,
+) {
+    parent
+        .spawn((
+            DifficultyButton,
+            ButtonBundle {
+                style: Style {
+                    size: Size::new(Val::Px(220.0), Val::Px(40.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.5)),
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    difficulty_label(difficulty),
+                    TextStyle {
+                        font: asset_server.load("century-gothic/gothic.ttf"),
+                        font_size: 25.0,
+                        color: Color::WHITE,
+                    },
+                ),
+                DifficultyText,
+            ));
+        });
+}
+
+//each click moves on to the next preset, wrapping back around to easy
+#[allow(clippy::type_complexity)]
+pub(super) fn pick_difficulty(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<DifficultyButton>),
+    >,
+    mut difficulty: ResMut<Difficulty>,
+) {
+    let Ok((interaction, mut background_color)) = button_query.get_single_mut() else {
+        return;
+    };
+
+    match *interaction {
+        Interaction::Clicked => {
+            *difficulty = difficulty.next();
+        }
+        Interaction::Hovered => {
+            *background_color = BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.8));
+        }
+        Interaction::None => {
+            *background_color = BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.5));
+        }
+    }
+}
+
+pub(super) fn update_difficulty_text(
+    difficulty: Res<Difficulty>,
+    mut text_query: Query<&mut Text, With<DifficultyText>>,
+) {
+    if !difficulty.is_changed() {
+        return;
+    }
+
+    for mut text in text_query.iter_mut() {
+        text.sections[0].value = difficulty_label(*difficulty);
+    }
+}
@@ -1,6 +1,17 @@
+mod difficulty_picker;
+mod seed_field;
+
 use bevy::{app::AppExit, prelude::*};
 
-use crate::{utils::interact_button, AppState, SettingsState};
+use crate::{
+    game::{difficulty::Difficulty, platform::chunk::CustomLayout, seed::ChosenSeed},
+    persistence::ConfigDir,
+    utils::interact_button,
+    AppState, SettingsState,
+};
+
+use self::difficulty_picker::{create_difficulty_picker, pick_difficulty, update_difficulty_text};
+use self::seed_field::{create_seed_field, focus_seed_field, type_seed, update_seed_field_text};
 
 pub struct MenuPlugin;
 
@@ -16,7 +27,23 @@ struct PlayButton;
 #[derive(Component)]
 struct SettingsButton;
 
-fn create_main_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+#[derive(Component)]
+struct HighScoresButton;
+
+#[derive(Component)]
+struct EditorButton;
+
+//plays the layout last saved in the editor, only there once something's been saved
+#[derive(Component)]
+struct CustomLayoutButton;
+
+fn create_main_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    chosen_seed: Res<ChosenSeed>,
+    difficulty: Res<Difficulty>,
+    config_dir: Res<ConfigDir>,
+) {
     commands
         .spawn((
             NodeBundle {
@@ -63,7 +90,7 @@ fn create_main_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
                             image: asset_server.load("menus/buttons/setting_icon.png").into(),
                             style: Style {
                                 size: Size::new(Val::Auto, Val::Percent(100.0)),
-                                aspect_ratio: Some(1.0 / 1.0),
+                                aspect_ratio: Some(1.0),
                                 ..default()
                             },
                             ..default()
@@ -119,6 +146,117 @@ fn create_main_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
                     });
                 });
 
+            create_seed_field(parent, &asset_server, &chosen_seed);
+
+            create_difficulty_picker(parent, &asset_server, *difficulty);
+
+            parent
+                .spawn((
+                    HighScoresButton,
+                    ButtonBundle {
+                        style: Style {
+                            size: Size::new(Val::Px(160.0), Val::Px(50.0)),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        image: UiImage {
+                            texture: asset_server.load("menus/buttons/blue_button.png"),
+                            ..default()
+                        },
+                        background_color: BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.0)),
+                        ..default()
+                    },
+                ))
+                .with_children(|parent| {
+                    parent.spawn(TextBundle {
+                        text: Text {
+                            sections: vec![TextSection::new(
+                                "Scores",
+                                TextStyle {
+                                    font: asset_server.load("century-gothic/gothic_bold.ttf"),
+                                    font_size: 30.0,
+                                    color: Color::ALICE_BLUE,
+                                },
+                            )],
+                            ..default()
+                        },
+                        ..default()
+                    });
+                });
+
+            parent
+                .spawn((
+                    EditorButton,
+                    ButtonBundle {
+                        style: Style {
+                            size: Size::new(Val::Px(160.0), Val::Px(50.0)),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        image: UiImage {
+                            texture: asset_server.load("menus/buttons/blue_button.png"),
+                            ..default()
+                        },
+                        background_color: BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.0)),
+                        ..default()
+                    },
+                ))
+                .with_children(|parent| {
+                    parent.spawn(TextBundle {
+                        text: Text {
+                            sections: vec![TextSection::new(
+                                "Editor",
+                                TextStyle {
+                                    font: asset_server.load("century-gothic/gothic_bold.ttf"),
+                                    font_size: 30.0,
+                                    color: Color::ALICE_BLUE,
+                                },
+                            )],
+                            ..default()
+                        },
+                        ..default()
+                    });
+                });
+
+            if CustomLayout::path(&config_dir).exists() {
+                parent
+                    .spawn((
+                        CustomLayoutButton,
+                        ButtonBundle {
+                            style: Style {
+                                size: Size::new(Val::Px(160.0), Val::Px(50.0)),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            image: UiImage {
+                                texture: asset_server.load("menus/buttons/blue_button.png"),
+                                ..default()
+                            },
+                            background_color: BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.0)),
+                            ..default()
+                        },
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle {
+                            text: Text {
+                                sections: vec![TextSection::new(
+                                    "Custom",
+                                    TextStyle {
+                                        font: asset_server.load("century-gothic/gothic_bold.ttf"),
+                                        font_size: 30.0,
+                                        color: Color::ALICE_BLUE,
+                                    },
+                                )],
+                                ..default()
+                            },
+                            ..default()
+                        });
+                    });
+            }
+
             parent
                 .spawn((
                     ExitButton,
@@ -160,6 +298,7 @@ fn despawn_main_menu(mut commands: Commands, main_menu: Query<Entity, With<MainM
     commands.entity(main_menu.single()).despawn_recursive();
 }
 
+#[allow(clippy::type_complexity)]
 fn interact_exit_button(
     mut button_query: Query<
         (&Interaction, &mut BackgroundColor),
@@ -190,6 +329,56 @@ impl interact_button::HoverButton for SettingsButton {
     }
 }
 
+impl interact_button::HoverButton for HighScoresButton {
+    fn on_click(commands: &mut Commands) {
+        commands.insert_resource(NextState(Some(AppState::HighScores)));
+    }
+    fn get_interaction_colors() -> interact_button::InteractionColors {
+        interact_button::InteractionColors {
+            hover_color: Color::rgba(1.0, 1.0, 1.0, 1.0),
+            normal_color: Color::rgba(0.95, 0.95, 0.95, 1.0),
+        }
+    }
+}
+
+impl interact_button::HoverButton for EditorButton {
+    fn on_click(commands: &mut Commands) {
+        commands.insert_resource(NextState(Some(AppState::Editor)));
+    }
+    fn get_interaction_colors() -> interact_button::InteractionColors {
+        interact_button::InteractionColors {
+            hover_color: Color::rgba(1.0, 1.0, 1.0, 1.0),
+            normal_color: Color::rgba(0.95, 0.95, 0.95, 1.0),
+        }
+    }
+}
+
+impl interact_button::HoverButton for CustomLayoutButton {
+    fn on_click(commands: &mut Commands) {
+        //the button only has commands, the file is found once they run
+        commands.add(|world: &mut World| {
+            let config_dir = world.resource::<ConfigDir>().clone();
+
+            match CustomLayout::read(&config_dir) {
+                Ok(layout) => {
+                    world.insert_resource(CustomLayout(Some(layout)));
+                    world.insert_resource(NextState(Some(AppState::InGame)));
+                }
+                Err(err) => warn!(
+                    "could not read {}: {err}",
+                    CustomLayout::path(&config_dir).display()
+                ),
+            }
+        });
+    }
+    fn get_interaction_colors() -> interact_button::InteractionColors {
+        interact_button::InteractionColors {
+            hover_color: Color::rgba(1.0, 1.0, 1.0, 1.0),
+            normal_color: Color::rgba(0.95, 0.95, 0.95, 1.0),
+        }
+    }
+}
+
 impl interact_button::HoverButton for PlayButton {
     fn on_click(commands: &mut Commands) {
         commands.insert_resource(NextState(Some(AppState::InGame)));
@@ -202,6 +391,12 @@ impl interact_button::HoverButton for PlayButton {
     }
 }
 
+//a layout from the editor only sticks around for its playtest, or until the run it was picked for
+//is over, so whichever way a run starts from here it's a generated one
+fn forget_custom_layout(mut commands: Commands) {
+    commands.insert_resource(CustomLayout::default());
+}
+
 fn hide_on_settings_open(mut main_menu: Query<&mut Visibility, With<MainMenu>>) {
     *main_menu.single_mut() = Visibility::Hidden;
 }
@@ -213,6 +408,7 @@ fn reveal_on_settings_close(mut main_menu: Query<&mut Visibility, With<MainMenu>
 impl Plugin for MenuPlugin {
     fn build(&self, app: &mut App) {
         app.add_system(create_main_menu.in_schedule(OnEnter(AppState::MainMenu)))
+            .add_system(forget_custom_layout.in_schedule(OnEnter(AppState::MainMenu)))
             .add_system(despawn_main_menu.in_schedule(OnExit(AppState::MainMenu)))
             .add_system(
                 hide_on_settings_open
@@ -228,7 +424,15 @@ impl Plugin for MenuPlugin {
                 (
                     interact_exit_button,
                     interact_button::interact_system::<PlayButton>,
+                    interact_button::interact_system::<HighScoresButton>,
+                    interact_button::interact_system::<EditorButton>,
+                    interact_button::interact_system::<CustomLayoutButton>,
                     interact_button::interact_system::<SettingsButton>,
+                    focus_seed_field,
+                    type_seed.after(focus_seed_field),
+                    update_seed_field_text.after(type_seed),
+                    pick_difficulty,
+                    update_difficulty_text.after(pick_difficulty),
                 )
                     .in_set(OnUpdate(AppState::MainMenu)),
             );
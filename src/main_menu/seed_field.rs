@@ -0,0 +1,136 @@
+use bevy::prelude::*;
+
+use crate::{game::seed::ChosenSeed, AppState};
+
+//u64 always fits this many digits
+const MAX_DIGITS: usize = 19;
+
+#[derive(Component)]
+pub(super) struct SeedField {
+    digits: String,
+    focused: bool,
+}
+
+#[derive(Component)]
+pub(super) struct SeedFieldText;
+
+pub(super) fn create_seed_field(
+    parent: &mut ChildBuilder,
+    asset_server: &AssetServer,
+    chosen_seed: &ChosenSeed,
+) {
+    parent
+        .spawn((
+            SeedField {
+                digits: chosen_seed
+                    .0
+                    .map(|seed| seed.to_string())
+                    .unwrap_or_default(),
+                focused: false,
+            },
+            ButtonBundle {
+                style: Style {
+                    size: Size::new(Val::Px(220.0), Val::Px(40.0)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.5)),
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font: asset_server.load("century-gothic/gothic.ttf"),
+                        font_size: 25.0,
+                        color: Color::WHITE,
+                    },
+                ),
+                SeedFieldText,
+            ));
+        });
+}
+
+//clicking the field starts typing, clicking anywhere else stops
+pub(super) fn focus_seed_field(
+    mut seed_field_query: Query<(&Interaction, &mut SeedField, &mut BackgroundColor)>,
+    mouse: Res<Input<MouseButton>>,
+) {
+    for (interaction, mut seed_field, mut background_color) in seed_field_query.iter_mut() {
+        if *interaction == Interaction::Clicked && !seed_field.focused {
+            seed_field.focused = true;
+        } else if mouse.just_pressed(MouseButton::Left) && *interaction != Interaction::Clicked {
+            seed_field.focused = false;
+        }
+
+        *background_color = BackgroundColor(if seed_field.focused {
+            Color::rgba(0.0, 0.0, 0.0, 0.8)
+        } else {
+            Color::rgba(0.0, 0.0, 0.0, 0.5)
+        });
+    }
+}
+
+pub(super) fn type_seed(
+    mut ev_characters: EventReader<ReceivedCharacter>,
+    keys: Res<Input<KeyCode>>,
+    mut seed_field_query: Query<&mut SeedField>,
+    mut chosen_seed: ResMut<ChosenSeed>,
+    mut commands: Commands,
+) {
+    let Ok(mut seed_field) = seed_field_query.get_single_mut() else {
+        return;
+    };
+
+    if !seed_field.focused {
+        ev_characters.clear();
+        return;
+    }
+
+    for character in ev_characters.iter() {
+        if character.char.is_ascii_digit() && seed_field.digits.len() < MAX_DIGITS {
+            seed_field.digits.push(character.char);
+        }
+    }
+
+    if keys.just_pressed(KeyCode::Back) {
+        seed_field.digits.pop();
+    }
+
+    //anything that doesn't parse (only too big numbers can) means random
+    chosen_seed.set_if_neq(ChosenSeed(seed_field.digits.parse().ok()));
+
+    if keys.just_pressed(KeyCode::Return) {
+        commands.insert_resource(NextState(Some(AppState::InGame)));
+    }
+}
+
+pub(super) fn update_seed_field_text(
+    seed_field_query: Query<(&SeedField, &Children), Changed<SeedField>>,
+    mut text_query: Query<&mut Text, With<SeedFieldText>>,
+) {
+    for (seed_field, children) in seed_field_query.iter() {
+        let mut text_iter = text_query.iter_many_mut(children);
+
+        let Some(mut text) = text_iter.fetch_next() else {
+            continue;
+        };
+
+        let section = &mut text.sections[0];
+
+        if seed_field.digits.is_empty() && !seed_field.focused {
+            section.value = "random seed".to_string();
+            section.style.color = Color::GRAY;
+        } else {
+            section.value = format!(
+                "{}{}",
+                seed_field.digits,
+                if seed_field.focused { "_" } else { "" }
+            );
+            section.style.color = Color::WHITE;
+        }
+    }
+}
@@ -0,0 +1,348 @@
+use std::{
+    fs, io,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+};
+
+use bevy::{app::AppExit, prelude::*};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+//how long a resource has to stay unchanged before it gets written,
+//dragging a slider changes it every frame
+const SAVE_DEBOUNCE_SECONDS: f32 = 0.5;
+
+pub trait Persistent: Resource + Default + Serialize + DeserializeOwned {
+    const FILE_NAME: &'static str;
+    const VERSION: u32;
+
+    //fix up values that parsed fine but make no sense, the file is user editable
+    fn sanitize(&mut self) {}
+}
+
+#[derive(Resource, Clone, Debug)]
+pub struct ConfigDir(pub PathBuf);
+
+impl Default for ConfigDir {
+    fn default() -> Self {
+        let base = dirs::config_dir().unwrap_or_else(|| PathBuf::from("."));
+        ConfigDir(base.join("bombcakes"))
+    }
+}
+
+#[derive(Serialize)]
+struct VersionedFileRef<'a, T> {
+    version: u32,
+    data: &'a T,
+}
+
+#[derive(Deserialize)]
+struct VersionHeader {
+    version: u32,
+}
+
+#[derive(Deserialize)]
+struct VersionedFile<T> {
+    data: T,
+}
+
+#[derive(Debug)]
+pub enum LoadError {
+    Missing,
+    Io(io::Error),
+    Parse(ron::error::SpannedError),
+    Version { found: u32, expected: u32 },
+}
+
+impl std::fmt::Display for LoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadError::Missing => write!(f, "file does not exist"),
+            LoadError::Io(err) => write!(f, "could not read file: {err}"),
+            LoadError::Parse(err) => write!(f, "could not parse file: {err}"),
+            LoadError::Version { found, expected } => {
+                write!(f, "file has version {found}, expected {expected}")
+            }
+        }
+    }
+}
+
+pub fn file_path<T: Persistent>(dir: &Path) -> PathBuf {
+    dir.join(T::FILE_NAME)
+}
+
+pub fn try_load<T: Persistent>(dir: &Path) -> Result<T, LoadError> {
+    //read as bytes, so a file that isn't even text counts as corrupt rather than unreadable
+    let contents = match fs::read(file_path::<T>(dir)) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Err(LoadError::Missing),
+        Err(err) => return Err(LoadError::Io(err)),
+    };
+
+    let header: VersionHeader = ron::de::from_bytes(&contents).map_err(LoadError::Parse)?;
+
+    if header.version != T::VERSION {
+        return Err(LoadError::Version {
+            found: header.version,
+            expected: T::VERSION,
+        });
+    }
+
+    let mut file: VersionedFile<T> = ron::de::from_bytes(&contents).map_err(LoadError::Parse)?;
+    file.data.sanitize();
+
+    Ok(file.data)
+}
+
+//never fails, anything unreadable is logged and replaced with the defaults.
+//the bad file is kept as .bak so the next save doesn't destroy it
+pub fn load_or_default<T: Persistent>(dir: &Path) -> T {
+    match try_load::<T>(dir) {
+        Ok(data) => data,
+        Err(LoadError::Missing) => T::default(),
+        Err(err) => {
+            let path = file_path::<T>(dir);
+            warn!("{}: {err}, falling back to defaults", path.display());
+
+            if !matches!(err, LoadError::Io(_)) {
+                if let Err(err) = fs::rename(&path, path.with_extension("bak")) {
+                    warn!("could not back up {}: {err}", path.display());
+                }
+            }
+
+            T::default()
+        }
+    }
+}
+
+pub fn save<T: Persistent>(dir: &Path, data: &T) -> io::Result<()> {
+    let contents = ron::ser::to_string_pretty(
+        &VersionedFileRef {
+            version: T::VERSION,
+            data,
+        },
+        ron::ser::PrettyConfig::default(),
+    )
+    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+    fs::create_dir_all(dir)?;
+
+    //write next to the real file first so a crash mid-write can't corrupt it
+    let path = file_path::<T>(dir);
+    let temp_path = path.with_extension("tmp");
+    fs::write(&temp_path, contents)?;
+    fs::rename(temp_path, path)
+}
+
+fn save_on_change<T: Persistent>(
+    data: Res<T>,
+    config_dir: Res<ConfigDir>,
+    time: Res<Time>,
+    ev_exit: EventReader<AppExit>,
+    mut debounce: Local<Option<Timer>>,
+) {
+    if data.is_changed() && !data.is_added() {
+        *debounce = Some(Timer::from_seconds(SAVE_DEBOUNCE_SECONDS, TimerMode::Once));
+    }
+
+    let Some(timer) = debounce.as_mut() else {
+        return;
+    };
+
+    timer.tick(time.delta());
+
+    if timer.finished() || !ev_exit.is_empty() {
+        if let Err(err) = save(&config_dir.0, &*data) {
+            error!(
+                "could not save {}: {err}",
+                file_path::<T>(&config_dir.0).display()
+            );
+        }
+
+        *debounce = None;
+    }
+}
+
+pub struct PersistPlugin<T: Persistent>(PhantomData<T>);
+
+impl<T: Persistent> Default for PersistPlugin<T> {
+    fn default() -> Self {
+        PersistPlugin(PhantomData)
+    }
+}
+
+impl<T: Persistent> Plugin for PersistPlugin<T> {
+    fn build(&self, app: &mut App) {
+        if !app.world.contains_resource::<ConfigDir>() {
+            app.init_resource::<ConfigDir>();
+        }
+
+        let config_dir = app.world.resource::<ConfigDir>().0.clone();
+
+        app.insert_resource(load_or_default::<T>(&config_dir))
+            .add_system(save_on_change::<T>.in_base_set(CoreSet::Last));
+    }
+}
+
+//a fresh directory per test so they can run in parallel, gone again once dropped
+#[cfg(test)]
+pub(crate) struct TempDir(pub PathBuf);
+
+#[cfg(test)]
+impl TempDir {
+    pub fn new(name: &str) -> Self {
+        let dir = std::env::temp_dir().join(format!("bombcakes-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        TempDir(dir)
+    }
+}
+
+#[cfg(test)]
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_audio::GameAudioSettings;
+
+    #[derive(Resource, Default, Debug, PartialEq, Serialize, Deserialize)]
+    struct TestSettings {
+        volume: f64,
+        name: String,
+    }
+
+    impl Persistent for TestSettings {
+        const FILE_NAME: &'static str = "test_settings.ron";
+        const VERSION: u32 = 2;
+
+        fn sanitize(&mut self) {
+            self.volume = self.volume.clamp(0.0, 1.0);
+        }
+    }
+
+    fn write(dir: &Path, contents: &str) {
+        fs::write(file_path::<TestSettings>(dir), contents).unwrap();
+    }
+
+    #[test]
+    fn round_trips_through_a_file() {
+        let dir = TempDir::new("persistence-round-trip");
+        let settings = TestSettings {
+            volume: 0.25,
+            name: "cupcake".to_string(),
+        };
+
+        save(&dir.0, &settings).unwrap();
+
+        assert_eq!(try_load::<TestSettings>(&dir.0).unwrap(), settings);
+        assert_eq!(load_or_default::<TestSettings>(&dir.0), settings);
+        assert!(!file_path::<TestSettings>(&dir.0)
+            .with_extension("tmp")
+            .exists());
+    }
+
+    #[test]
+    fn missing_file_is_the_defaults() {
+        let dir = TempDir::new("persistence-missing");
+
+        assert!(matches!(
+            try_load::<TestSettings>(&dir.0),
+            Err(LoadError::Missing)
+        ));
+        assert_eq!(
+            load_or_default::<TestSettings>(&dir.0),
+            TestSettings::default()
+        );
+        assert!(!file_path::<TestSettings>(&dir.0)
+            .with_extension("bak")
+            .exists());
+    }
+
+    #[test]
+    fn corrupt_file_falls_back_and_is_kept() {
+        let dir = TempDir::new("persistence-corrupt");
+        let garbage = "(version: 2, data: (volume: 0.5, name: \"unfinish";
+        write(&dir.0, garbage);
+
+        assert!(matches!(
+            try_load::<TestSettings>(&dir.0),
+            Err(LoadError::Parse(_))
+        ));
+        assert_eq!(
+            load_or_default::<TestSettings>(&dir.0),
+            TestSettings::default()
+        );
+
+        let backup = file_path::<TestSettings>(&dir.0).with_extension("bak");
+        assert_eq!(fs::read_to_string(backup).unwrap(), garbage);
+        assert!(!file_path::<TestSettings>(&dir.0).exists());
+    }
+
+    #[test]
+    fn binary_file_falls_back_and_is_kept() {
+        let dir = TempDir::new("persistence-binary");
+        let path = file_path::<TestSettings>(&dir.0);
+        fs::write(&path, [0xff, 0xfe, 0x00, 0x28, 0x80]).unwrap();
+
+        assert!(matches!(
+            try_load::<TestSettings>(&dir.0),
+            Err(LoadError::Parse(_))
+        ));
+        assert_eq!(
+            load_or_default::<TestSettings>(&dir.0),
+            TestSettings::default()
+        );
+        assert!(path.with_extension("bak").exists());
+    }
+
+    #[test]
+    fn other_version_falls_back_and_is_kept() {
+        let dir = TempDir::new("persistence-version");
+        write(&dir.0, "(version: 1, data: (volume: 0.5, name: \"old\"))");
+
+        assert!(matches!(
+            try_load::<TestSettings>(&dir.0),
+            Err(LoadError::Version {
+                found: 1,
+                expected: 2
+            })
+        ));
+        assert_eq!(
+            load_or_default::<TestSettings>(&dir.0),
+            TestSettings::default()
+        );
+        assert!(file_path::<TestSettings>(&dir.0)
+            .with_extension("bak")
+            .exists());
+    }
+
+    #[test]
+    fn out_of_range_values_are_sanitized() {
+        let dir = TempDir::new("persistence-sanitize");
+        write(&dir.0, "(version: 2, data: (volume: 7.0, name: \"loud\"))");
+
+        let settings = load_or_default::<TestSettings>(&dir.0);
+        assert_eq!(settings.volume, 1.0);
+        assert_eq!(settings.name, "loud");
+    }
+
+    #[test]
+    fn audio_volumes_are_sanitized() {
+        let dir = TempDir::new("persistence-audio-sanitize");
+        fs::write(
+            file_path::<GameAudioSettings>(&dir.0),
+            "(version: 1, data: (master: 7.0, music: NaN, sfx: -0.5, sfx_muted: true))",
+        )
+        .unwrap();
+
+        let settings = load_or_default::<GameAudioSettings>(&dir.0);
+        assert_eq!(settings.master, 1.0);
+        assert_eq!(settings.music, 1.0);
+        assert_eq!(settings.sfx, 0.0);
+        assert!(settings.sfx_muted);
+    }
+}
@@ -0,0 +1,188 @@
+use bevy::{input::InputSystem, prelude::*, ui::UiSystem};
+
+use crate::{
+    actions::{Action, Binding, Bindings},
+    utils::interact_button::{self, HoverButton},
+    SettingsState,
+};
+
+use super::{create_settings_button, create_settings_page, SettingsPage};
+
+#[derive(Component)]
+struct ControlsBackButton;
+
+impl HoverButton for ControlsBackButton {
+    fn on_click(commands: &mut Commands) {
+        commands.insert_resource(NextState(Some(SettingsPage::General)));
+    }
+}
+
+#[derive(Component)]
+struct RebindButton(Action);
+
+//the action waiting for its next key or mouse press
+#[derive(Resource, Default)]
+struct ListeningForBinding(Option<Action>);
+
+fn create_controls_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+    create_settings_page(
+        &mut commands,
+        &asset_server,
+        "Controls",
+        ControlsBackButton,
+        |parent| {
+            for action in Action::ALL {
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            display: Display::Flex,
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            gap: Size::width(Val::Px(10.0)),
+                            ..default()
+                        },
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section(
+                            action.label(),
+                            TextStyle {
+                                font: asset_server.load("century-gothic/gothic.ttf"),
+                                font_size: 23.0,
+                                color: Color::WHITE,
+                            },
+                        ));
+
+                        //label gets filled in by update_rebind_labels
+                        create_settings_button(parent, &asset_server, "", RebindButton(action));
+                    });
+            }
+
+            //aiming follows the cursor or the right stick and can't be rebound, it's listed so it isn't missed
+            parent.spawn(TextBundle::from_section(
+                "Aim: Mouse / Right stick",
+                TextStyle {
+                    font: asset_server.load("century-gothic/gothic.ttf"),
+                    font_size: 23.0,
+                    color: Color::WHITE,
+                },
+            ));
+        },
+    );
+}
+
+fn interact_rebind_button(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor, &RebindButton),
+        Changed<Interaction>,
+    >,
+    mut listening: ResMut<ListeningForBinding>,
+) {
+    for (interaction, mut background_color, rebind_button) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Clicked => {
+                listening.0 = Some(rebind_button.0);
+            }
+            Interaction::Hovered => {
+                *background_color = BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 1.0));
+            }
+            Interaction::None => {
+                *background_color = BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.8));
+            }
+        }
+    }
+}
+
+//runs before anything else reads input, the press is consumed so it doesn't
+//also pause the game or click a button
+fn capture_binding(
+    mut listening: ResMut<ListeningForBinding>,
+    mut bindings: ResMut<Bindings>,
+    mut keys: ResMut<Input<KeyCode>>,
+    mut mouse: ResMut<Input<MouseButton>>,
+) {
+    let Some(action) = listening.0 else {
+        return;
+    };
+
+    let pressed_key = keys.get_just_pressed().next().copied();
+    let pressed_button = mouse.get_just_pressed().next().copied();
+
+    let binding = if let Some(key) = pressed_key {
+        keys.clear_just_pressed(key);
+        Binding::Key(key)
+    } else if let Some(button) = pressed_button {
+        mouse.clear_just_pressed(button);
+        Binding::Mouse(button)
+    } else {
+        return;
+    };
+
+    //whatever was on that input before takes this action's old binding
+    let previous = bindings.get(action);
+    for other in Action::ALL {
+        if other != action && bindings.get(other) == binding {
+            bindings.set(other, previous);
+        }
+    }
+
+    bindings.set(action, binding);
+    listening.0 = None;
+}
+
+fn update_rebind_labels(
+    bindings: Res<Bindings>,
+    listening: Res<ListeningForBinding>,
+    button_query: Query<(Ref<RebindButton>, &Children)>,
+    mut text_query: Query<&mut Text>,
+) {
+    for (rebind_button, children) in button_query.iter() {
+        if !bindings.is_changed() && !listening.is_changed() && !rebind_button.is_added() {
+            continue;
+        }
+
+        let label = if listening.0 == Some(rebind_button.0) {
+            "press a key...".to_string()
+        } else {
+            bindings.get(rebind_button.0).label()
+        };
+
+        for child in children.iter() {
+            if let Ok(mut text) = text_query.get_mut(*child) {
+                text.sections[0].value = label.clone();
+            }
+        }
+    }
+}
+
+fn stop_listening(mut listening: ResMut<ListeningForBinding>) {
+    listening.0 = None;
+}
+
+pub(super) struct ControlsPlugin;
+
+impl Plugin for ControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ListeningForBinding>()
+            .add_system(
+                create_controls_menu
+                    .in_schedule(OnEnter(SettingsPage::Controls))
+                    .run_if(in_state(SettingsState::Open)),
+            )
+            .add_system(stop_listening.in_schedule(OnExit(SettingsPage::Controls)))
+            .add_system(
+                capture_binding
+                    .in_base_set(CoreSet::PreUpdate)
+                    .after(InputSystem)
+                    .before(UiSystem::Focus),
+            )
+            .add_systems(
+                (
+                    interact_button::interact_system::<ControlsBackButton>,
+                    interact_rebind_button,
+                    update_rebind_labels,
+                )
+                    .in_set(OnUpdate(SettingsPage::Controls)),
+            );
+    }
+}
@@ -1,8 +1,12 @@
 use bevy::prelude::*;
 
-use self::slider::{drag_slider, start_drag_slider, SliderHandle};
+use self::{
+    slider::{drag_slider, start_drag_slider, SliderHandle},
+    toggle::{click_toggle, show_toggle, ToggleHandle},
+};
 
 pub mod slider;
+pub mod toggle;
 
 pub struct InputPlugin;
 
@@ -10,6 +14,9 @@ impl Plugin for InputPlugin {
     fn build(&self, app: &mut App) {
         app.add_system(drag_slider)
             .add_system(start_drag_slider)
-            .register_type::<SliderHandle>();
+            .add_system(click_toggle)
+            .add_system(show_toggle.after(click_toggle))
+            .register_type::<SliderHandle>()
+            .register_type::<ToggleHandle>();
     }
 }
@@ -1,5 +1,7 @@
 use bevy::{prelude::*, window::PrimaryWindow};
 
+use crate::utils::gamepad_navigation::SkipGamepadFocus;
+
 #[derive(Component, Reflect)]
 pub struct SliderHandle {
     pub position: f32,
@@ -106,6 +108,7 @@ pub fn create_slider(
                             drag_start: None,
                             just_created: true,
                         },
+                        SkipGamepadFocus,
                         bundle,
                     ));
 
@@ -157,6 +160,7 @@ pub(super) fn start_drag_slider(
     }
 }
 
+#[allow(clippy::type_complexity)]
 pub(super) fn drag_slider(
     slider_back_query: Query<
         Entity,
@@ -225,7 +229,7 @@ pub(super) fn drag_slider(
 
         let distance = (cursor_position.x - drag_start) / 190.0;
 
-        let handle_display_pos = (handle.position + distance).min(1.0).max(0.0) * 190.0;
+        let handle_display_pos = (handle.position + distance).clamp(0.0, 1.0) * 190.0;
 
         handle_style.position = UiRect::new(
             Val::Px(handle_display_pos),
@@ -238,7 +242,7 @@ pub(super) fn drag_slider(
 
         if mouse.just_released(MouseButton::Left) {
             handle.position += distance;
-            handle.position = handle.position.min(1.0).max(0.0);
+            handle.position = handle.position.clamp(0.0, 1.0);
             handle.drag_start = None;
         }
     }
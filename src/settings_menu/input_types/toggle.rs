@@ -0,0 +1,113 @@
+use bevy::prelude::*;
+
+#[derive(Component, Reflect)]
+pub struct ToggleHandle {
+    pub on: bool,
+    pub just_created: bool,
+}
+
+//the box inside the toggle that's filled in while it's on
+#[derive(Component)]
+pub(super) struct ToggleCheck;
+
+pub trait ToggleDataController<Data: Resource> {
+    fn save_data(data: &mut Data, on: bool);
+    fn load_data(data: &Data) -> bool;
+}
+
+pub fn create_toggle(
+    asset_server: &AssetServer,
+    parent: &mut ChildBuilder,
+    label: &str,
+    bundle: impl Bundle,
+) {
+    parent
+        .spawn(NodeBundle {
+            style: Style {
+                display: Display::Flex,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                label,
+                TextStyle {
+                    font: asset_server.load("century-gothic/gothic.ttf"),
+                    font_size: 23.0,
+                    color: Color::WHITE,
+                },
+            ));
+
+            parent
+                .spawn((
+                    ButtonBundle {
+                        background_color: BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.8)),
+                        style: Style {
+                            size: Size::new(Val::Px(24.0), Val::Px(24.0)),
+                            margin: UiRect::all(Val::Px(10.0)),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    ToggleHandle {
+                        on: false,
+                        just_created: true,
+                    },
+                    bundle,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        NodeBundle {
+                            style: Style {
+                                size: Size::new(Val::Px(14.0), Val::Px(14.0)),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        ToggleCheck,
+                    ));
+                });
+        });
+}
+
+pub(super) fn click_toggle(
+    mut toggle_query: Query<
+        (&Interaction, &mut BackgroundColor, &mut ToggleHandle),
+        Changed<Interaction>,
+    >,
+) {
+    for (interaction, mut background_color, mut toggle_handle) in toggle_query.iter_mut() {
+        match *interaction {
+            Interaction::Clicked => {
+                toggle_handle.on = !toggle_handle.on;
+            }
+            Interaction::Hovered => {
+                *background_color = BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 1.0));
+            }
+            Interaction::None => {
+                *background_color = BackgroundColor(Color::rgba(0.0, 0.0, 0.0, 0.8));
+            }
+        }
+    }
+}
+
+pub(super) fn show_toggle(
+    toggle_query: Query<(&ToggleHandle, &Children), Changed<ToggleHandle>>,
+    mut check_query: Query<&mut BackgroundColor, (With<ToggleCheck>, Without<ToggleHandle>)>,
+) {
+    for (toggle_handle, children) in toggle_query.iter() {
+        let mut checks = check_query.iter_many_mut(children);
+        while let Some(mut check) = checks.fetch_next() {
+            *check = BackgroundColor(if toggle_handle.on {
+                Color::WHITE
+            } else {
+                Color::NONE
+            });
+        }
+    }
+}
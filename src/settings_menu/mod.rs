@@ -6,10 +6,12 @@ use crate::{
     SettingsState,
 };
 
+mod controls;
 mod input_types;
 
 use self::input_types::{
     slider::{create_slider, SliderDataController, SliderHandle},
+    toggle::{create_toggle, ToggleDataController, ToggleHandle},
     InputPlugin,
 };
 
@@ -34,7 +36,42 @@ struct SFXVolumeSlider;
 #[derive(Component)]
 struct MusicVolumeSlider;
 
-fn create_settings_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+#[derive(Component)]
+struct MuteMusicToggle;
+
+#[derive(Component)]
+struct MuteSFXToggle;
+
+#[derive(Component)]
+struct PositionalAudioToggle;
+
+#[derive(Component)]
+struct MuteWhenUnfocusedToggle;
+
+#[derive(Component)]
+struct ControlsButton;
+
+impl HoverButton for ControlsButton {
+    fn on_click(commands: &mut Commands) {
+        commands.insert_resource(NextState(Some(SettingsPage::Controls)));
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, States)]
+enum SettingsPage {
+    #[default]
+    General,
+    Controls,
+}
+
+//full screen page with a back arrow and a title, content goes in the column below
+fn create_settings_page(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    title: &str,
+    back_button: impl Bundle,
+    content: impl FnOnce(&mut ChildBuilder),
+) {
     commands
         .spawn((
             NodeBundle {
@@ -80,7 +117,7 @@ fn create_settings_menu(mut commands: Commands, asset_server: Res<AssetServer>)
                             image: asset_server.load("menus/buttons/back_arrow.png").into(),
                             ..default()
                         },
-                        BackButton,
+                        back_button,
                     ));
 
                     parent
@@ -96,7 +133,7 @@ fn create_settings_menu(mut commands: Commands, asset_server: Res<AssetServer>)
                         .with_children(|parent| {
                             parent.spawn(
                                 TextBundle::from_section(
-                                    "Settings",
+                                    title,
                                     TextStyle {
                                         font: asset_server.load("century-gothic/gothic.ttf"),
                                         font_size: 60.0,
@@ -140,20 +177,73 @@ fn create_settings_menu(mut commands: Commands, asset_server: Res<AssetServer>)
                             },
                             ..default()
                         })
-                        .with_children(|parent| {
-                            create_slider(
-                                &asset_server,
-                                parent,
-                                "Master volume",
-                                MasterVolumeSlider,
-                            );
-                            create_slider(&asset_server, parent, "Music volume", MusicVolumeSlider);
-                            create_slider(&asset_server, parent, "SFX volume", SFXVolumeSlider);
-                        });
+                        .with_children(content);
                 });
         });
 }
 
+fn create_settings_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
+    create_settings_page(
+        &mut commands,
+        &asset_server,
+        "Settings",
+        BackButton,
+        |parent| {
+            create_slider(&asset_server, parent, "Master volume", MasterVolumeSlider);
+            create_slider(&asset_server, parent, "Music volume", MusicVolumeSlider);
+            create_slider(&asset_server, parent, "SFX volume", SFXVolumeSlider);
+
+            create_toggle(&asset_server, parent, "Mute music", MuteMusicToggle);
+            create_toggle(&asset_server, parent, "Mute SFX", MuteSFXToggle);
+            create_toggle(
+                &asset_server,
+                parent,
+                "Positional audio",
+                PositionalAudioToggle,
+            );
+            create_toggle(
+                &asset_server,
+                parent,
+                "Mute when unfocused",
+                MuteWhenUnfocusedToggle,
+            );
+
+            create_settings_button(parent, &asset_server, "Controls", ControlsButton);
+        },
+    );
+}
+
+fn create_settings_button(
+    parent: &mut ChildBuilder,
+    asset_server: &AssetServer,
+    label: &str,
+    bundle: impl Bundle,
+) {
+    parent
+        .spawn((
+            ButtonBundle {
+                background_color: BackgroundColor(Color::BLACK),
+                style: Style {
+                    padding: UiRect::all(Val::Px(10.0)),
+                    margin: UiRect::vertical(Val::Px(10.0)),
+                    ..default()
+                },
+                ..default()
+            },
+            bundle,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                label,
+                TextStyle {
+                    font: asset_server.load("century-gothic/gothic.ttf"),
+                    color: Color::WHITE,
+                    font_size: 23.0,
+                },
+            ));
+        });
+}
+
 fn update_slider_data<Data: Resource, Slider: Component + SliderDataController<Data>>(
     mut slider: Query<&mut SliderHandle, (Changed<SliderHandle>, With<Slider>)>,
     mut slider_controlling_data: ResMut<Data>,
@@ -195,24 +285,96 @@ impl SliderDataController<GameAudioSettings> for MusicVolumeSlider {
     }
 }
 
+fn update_toggle_data<Data: Resource, Toggle: Component + ToggleDataController<Data>>(
+    mut toggle: Query<&mut ToggleHandle, (Changed<ToggleHandle>, With<Toggle>)>,
+    mut toggle_controlling_data: ResMut<Data>,
+) {
+    if let Ok(mut data) = toggle.get_single_mut() {
+        if data.just_created {
+            data.on = Toggle::load_data(&toggle_controlling_data);
+            data.just_created = false;
+        } else {
+            Toggle::save_data(&mut toggle_controlling_data, data.on);
+        }
+    }
+}
+
+impl ToggleDataController<GameAudioSettings> for MuteMusicToggle {
+    fn load_data(data: &GameAudioSettings) -> bool {
+        data.music_muted
+    }
+    fn save_data(data: &mut GameAudioSettings, on: bool) {
+        data.music_muted = on;
+    }
+}
+
+impl ToggleDataController<GameAudioSettings> for MuteSFXToggle {
+    fn load_data(data: &GameAudioSettings) -> bool {
+        data.sfx_muted
+    }
+    fn save_data(data: &mut GameAudioSettings, on: bool) {
+        data.sfx_muted = on;
+    }
+}
+
+impl ToggleDataController<GameAudioSettings> for PositionalAudioToggle {
+    fn load_data(data: &GameAudioSettings) -> bool {
+        data.positional
+    }
+    fn save_data(data: &mut GameAudioSettings, on: bool) {
+        data.positional = on;
+    }
+}
+
+impl ToggleDataController<GameAudioSettings> for MuteWhenUnfocusedToggle {
+    fn load_data(data: &GameAudioSettings) -> bool {
+        data.mute_when_unfocused
+    }
+    fn save_data(data: &mut GameAudioSettings, on: bool) {
+        data.mute_when_unfocused = on;
+    }
+}
+
 fn despawn_settings_menu(mut commands: Commands, settings_menu: Query<Entity, With<SettingsMenu>>) {
     if let Ok(settings_entity) = settings_menu.get_single() {
         commands.entity(settings_entity).despawn_recursive();
     }
 }
 
+fn reset_settings_page(mut commands: Commands) {
+    commands.insert_resource(NextState(Some(SettingsPage::General)));
+}
+
 pub struct SettingsPlugin;
 
 impl Plugin for SettingsPlugin {
     fn build(&self, app: &mut App) {
-        app.add_system(create_settings_menu.in_schedule(OnEnter(SettingsState::Open)))
-            .add_system(despawn_settings_menu.in_schedule(OnEnter(SettingsState::Closed)))
+        app.add_state::<SettingsPage>()
+            .add_system(create_settings_menu.in_schedule(OnEnter(SettingsState::Open)))
+            .add_systems(
+                (despawn_settings_menu, reset_settings_page)
+                    .in_schedule(OnEnter(SettingsState::Closed)),
+            )
+            .add_system(despawn_settings_menu.in_schedule(OnEnter(SettingsPage::Controls)))
+            .add_systems(
+                (
+                    despawn_settings_menu,
+                    create_settings_menu.run_if(in_state(SettingsState::Open)),
+                )
+                    .in_schedule(OnExit(SettingsPage::Controls)),
+            )
             .add_systems((
                 interact_button::interact_system::<BackButton>,
+                interact_button::interact_system::<ControlsButton>,
+                update_toggle_data::<GameAudioSettings, MuteMusicToggle>,
+                update_toggle_data::<GameAudioSettings, MuteSFXToggle>,
+                update_toggle_data::<GameAudioSettings, PositionalAudioToggle>,
+                update_toggle_data::<GameAudioSettings, MuteWhenUnfocusedToggle>,
                 update_slider_data::<GameAudioSettings, MasterVolumeSlider>,
                 update_slider_data::<GameAudioSettings, SFXVolumeSlider>,
                 update_slider_data::<GameAudioSettings, MusicVolumeSlider>,
             ))
-            .add_plugin(InputPlugin);
+            .add_plugin(InputPlugin)
+            .add_plugin(controls::ControlsPlugin);
     }
 }
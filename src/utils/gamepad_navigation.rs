@@ -0,0 +1,114 @@
+use bevy::{prelude::*, ui::UiSystem};
+
+const STICK_THRESHOLD: f32 = 0.5;
+
+//buttons that shouldn't be reachable with the d-pad, like slider handles
+#[derive(Component)]
+pub struct SkipGamepadFocus;
+
+#[derive(Resource, Default)]
+struct GamepadFocus(Option<Entity>);
+
+//drives Interaction on the focused button the same way the mouse would,
+//so every HoverButton works with a gamepad without knowing about it
+#[allow(clippy::type_complexity)]
+fn navigate_with_gamepad(
+    mut focus: ResMut<GamepadFocus>,
+    gamepads: Res<Gamepads>,
+    gamepad_buttons: Res<Input<GamepadButton>>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    mut ev_cursor_moved: EventReader<CursorMoved>,
+    mut buttons: Query<
+        (
+            Entity,
+            &GlobalTransform,
+            &ComputedVisibility,
+            &mut Interaction,
+        ),
+        (With<Button>, Without<SkipGamepadFocus>),
+    >,
+    mut stick_was_pushed: Local<bool>,
+) {
+    if !ev_cursor_moved.is_empty() {
+        ev_cursor_moved.clear();
+        focus.0 = None;
+        return;
+    }
+
+    let any_just_pressed = |button_type| {
+        gamepads
+            .iter()
+            .any(|gamepad| gamepad_buttons.just_pressed(GamepadButton::new(gamepad, button_type)))
+    };
+
+    let stick_y = gamepads
+        .iter()
+        .filter_map(|gamepad| {
+            gamepad_axes.get(GamepadAxis::new(gamepad, GamepadAxisType::LeftStickY))
+        })
+        .find(|y| y.abs() > STICK_THRESHOLD);
+    let stick_just_pushed = stick_y.filter(|_| !*stick_was_pushed);
+    *stick_was_pushed = stick_y.is_some();
+
+    let step = if any_just_pressed(GamepadButtonType::DPadUp)
+        || stick_just_pushed.is_some_and(|y| y > 0.0)
+    {
+        -1
+    } else if any_just_pressed(GamepadButtonType::DPadDown)
+        || stick_just_pushed.is_some_and(|y| y < 0.0)
+    {
+        1
+    } else {
+        0
+    };
+    let confirm = any_just_pressed(GamepadButtonType::South);
+
+    //ui y grows downwards so this is top to bottom, left to right
+    let mut visible: Vec<(Entity, Vec3)> = buttons
+        .iter()
+        .filter(|(_, _, visibility, _)| visibility.is_visible())
+        .map(|(entity, transform, _, _)| (entity, transform.translation()))
+        .collect();
+    visible.sort_by(|(_, a), (_, b)| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)));
+
+    let current = focus
+        .0
+        .and_then(|focused| visible.iter().position(|(entity, _)| *entity == focused));
+
+    let next = match (current, step) {
+        (_, _) if visible.is_empty() => None,
+        (None, 0) => None,
+        (None, _) => Some(0),
+        (Some(index), step) => {
+            Some((index as i32 + step).rem_euclid(visible.len() as i32) as usize)
+        }
+    };
+
+    focus.0 = next.map(|index| visible[index].0);
+
+    let Some(focused) = focus.0 else {
+        return;
+    };
+
+    let Ok((_, _, _, mut interaction)) = buttons.get_mut(focused) else {
+        return;
+    };
+
+    if confirm {
+        *interaction = Interaction::Clicked;
+    } else if *interaction != Interaction::Hovered {
+        *interaction = Interaction::Hovered;
+    }
+}
+
+pub struct GamepadNavigationPlugin;
+
+impl Plugin for GamepadNavigationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GamepadFocus>().add_system(
+            navigate_with_gamepad
+                .in_base_set(CoreSet::PreUpdate)
+                .after(UiSystem::Focus),
+        );
+    }
+}
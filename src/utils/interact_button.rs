@@ -1,5 +1,7 @@
 use bevy::prelude::*;
 
+use crate::game_audio::{PlaySfx, SfxCue};
+
 pub trait HoverButton {
     fn on_click(commands: &mut Commands);
     fn get_interaction_colors() -> InteractionColors {
@@ -15,9 +17,12 @@ pub struct InteractionColors {
     pub normal_color: Color,
 }
 
+#[allow(clippy::type_complexity)]
 pub fn interact_system<T: HoverButton + bevy::prelude::Component>(
     mut button_query: Query<(&Interaction, &mut BackgroundColor), (Changed<Interaction>, With<T>)>,
     mut commands: Commands,
+    mut ev_sfx: EventWriter<PlaySfx>,
+    mut last_interaction: Local<Interaction>,
 ) {
     let Ok((interaction, mut background_color)) = button_query.get_single_mut() else {
       return;
@@ -27,9 +32,19 @@ pub fn interact_system<T: HoverButton + bevy::prelude::Component>(
 
     match *interaction {
         Interaction::Clicked => {
+            ev_sfx.send(PlaySfx::flat(SfxCue::ButtonClick));
             T::on_click(&mut commands);
         }
-        Interaction::Hovered => *background_color = BackgroundColor(colors.hover_color),
+        Interaction::Hovered => {
+            //letting go of a click goes back to hovered, that's not a new hover
+            if *last_interaction == Interaction::None {
+                ev_sfx.send(PlaySfx::flat(SfxCue::ButtonHover));
+            }
+
+            *background_color = BackgroundColor(colors.hover_color)
+        }
         Interaction::None => *background_color = BackgroundColor(colors.normal_color),
     }
+
+    *last_interaction = *interaction;
 }
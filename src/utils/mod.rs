@@ -1 +1,3 @@
+pub mod gamepad_navigation;
 pub mod interact_button;
+pub mod ron_asset;
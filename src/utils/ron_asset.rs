@@ -0,0 +1,57 @@
+use std::marker::PhantomData;
+
+use bevy::{
+    asset::{AssetLoader, LoadContext, LoadedAsset},
+    prelude::*,
+    reflect::TypeUuid,
+    utils::BoxedFuture,
+};
+use serde::de::DeserializeOwned;
+
+//loads any deserializable asset from a .ron file, the extension is
+//compound (like "weapons.ron") so every asset type gets its own loader
+pub struct RonAssetPlugin<T> {
+    extensions: &'static [&'static str],
+    _marker: PhantomData<T>,
+}
+
+impl<T> RonAssetPlugin<T> {
+    pub fn new(extensions: &'static [&'static str]) -> Self {
+        RonAssetPlugin {
+            extensions,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: TypeUuid + DeserializeOwned + Send + Sync + 'static> Plugin for RonAssetPlugin<T> {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<T>().add_asset_loader(RonAssetLoader::<T> {
+            extensions: self.extensions,
+            _marker: PhantomData,
+        });
+    }
+}
+
+struct RonAssetLoader<T> {
+    extensions: &'static [&'static str],
+    _marker: PhantomData<T>,
+}
+
+impl<T: TypeUuid + DeserializeOwned + Send + Sync + 'static> AssetLoader for RonAssetLoader<T> {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let asset = ron::de::from_bytes::<T>(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(asset));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        self.extensions
+    }
+}